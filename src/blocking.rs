@@ -0,0 +1,105 @@
+use std::future::Future;
+
+use crate::query::{FindQuery, SearchQuery};
+use crate::{Error, Movie, SearchResults, SearchResultsMovie};
+
+impl FindQuery {
+    /// Perform the same request as `get`, blocking until it's done.
+    ///
+    /// This starts its own runtime, so it panics if called from within
+    /// an async runtime.
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let movie = omdb::imdb_id("tt0032138")
+    ///     .apikey("1a2b3c4d")
+    ///     .get_blocking()
+    ///     .unwrap();
+    ///
+    /// assert!(movie.title == "The Wizard of Oz");
+    /// ```
+    pub fn get_blocking(&self) -> Result<Movie, Error> {
+        block_on(self.get())
+    }
+}
+
+impl SearchQuery {
+    /// Perform the same request as `get`, blocking until it's done.
+    ///
+    /// Like `FindQuery::get_blocking`, this panics if called from within
+    /// an async runtime.
+    pub fn get_blocking(&self) -> Result<SearchResults, Error> {
+        block_on(self.get())
+    }
+
+    /// Fetch every page of results like `get_all`, blocking until
+    /// they've all been fetched.
+    ///
+    /// Like `get_all`, the pages share one client and its connections.
+    pub fn get_all_blocking(&self) -> Result<Vec<SearchResultsMovie>, Error> {
+        block_on(self.get_all())
+    }
+}
+
+/// Runs `future` to completion on a new single threaded runtime.
+fn block_on<T, F>(future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|_| Error::Other("failed to start a runtime"))?
+        .block_on(future)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::{imdb_id, search};
+    use crate::Error;
+
+    #[test]
+    fn blocking() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+        ]);
+
+        let movie = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_blocking()
+            .unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        let results = search("zzzzzz")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_blocking();
+        assert!(matches!(results, Err(Error::Api(_))));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn get_all_blocking() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Search":[
+                {"Title":"Batman Begins","Year":"2005","imdbID":"tt0372784","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"11","Response":"True"}"#,
+            r#"{"Search":[
+                {"Title":"Batman","Year":"1989","imdbID":"tt0096895","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"11","Response":"True"}"#,
+        ]);
+
+        let results = search("batman")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_all_blocking()
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].contains("&page=2 "));
+    }
+}
@@ -0,0 +1,735 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::query::{
+    self, BatchQuery, Cache, CacheBackend, FindQuery, Http, Quota, RateLimiter, SearchQuery,
+};
+use crate::{Error, Kind, Movie, Plot, PosterImage, Transport};
+
+/// A reusable OMDb client holding an API key.
+///
+/// Queries created by a `Client` already have its API key, and share
+/// one connection pool, so cloning a `Client` is cheap.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), omdb::Error> {
+/// let client = omdb::Client::new("1a2b3c4d");
+///
+/// let movie = client.title("The Wizard of Oz").year(1939).get().await?;
+/// let results = client.search("batman").get().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Client {
+    apikey: String,
+    http: reqwest::Client,
+    base_url: Option<String>,
+    api_version: Option<String>,
+    user_agent: Option<String>,
+    limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<Cache>>,
+    cache_backend: Option<Arc<dyn CacheBackend>>,
+    quota: Arc<Quota>,
+    transport: Option<Arc<dyn Transport>>,
+    resolve: Vec<(String, SocketAddr)>,
+    max_response_bytes: Option<usize>,
+    plot_defaults: Vec<(Kind, Plot)>,
+    // How `http` was built, unless it was given to `with_client`
+    builder: Option<HttpOptions>,
+}
+
+/// The options a `Client` builds its `reqwest::Client` with.
+#[derive(Clone, Debug)]
+struct HttpOptions {
+    proxy: Option<reqwest::Proxy>,
+    compression: bool,
+    http2_prior_knowledge: bool,
+    tcp_keepalive: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+}
+
+impl HttpOptions {
+    fn new(proxy: Option<reqwest::Proxy>) -> HttpOptions {
+        HttpOptions {
+            proxy,
+            compression: true,
+            http2_prior_knowledge: false,
+            tcp_keepalive: None,
+            pool_idle_timeout: None,
+        }
+    }
+
+    fn build(&self) -> Result<reqwest::Client, Error> {
+        let mut builder = query::default_client_builder()
+            .gzip(self.compression)
+            .brotli(self.compression)
+            .tcp_keepalive(self.tcp_keepalive);
+        if let Some(proxy) = self.proxy.clone() {
+            builder = builder.proxy(proxy);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        // Otherwise reqwest's own default applies
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+impl Client {
+    /// Create a client sending `apikey` with every query.
+    pub fn new<S: ToString>(apikey: S) -> Client {
+        Client {
+            builder: Some(HttpOptions::new(None)),
+            ..Client::with_client(apikey, query::default_client())
+        }
+    }
+
+    /// Create a client sending `apikey` with every query, using `http`
+    /// to send requests.
+    pub fn with_client<S: ToString>(apikey: S, http: reqwest::Client) -> Client {
+        Client {
+            apikey: apikey.to_string(),
+            http,
+            base_url: None,
+            api_version: None,
+            user_agent: None,
+            limiter: None,
+            cache: None,
+            cache_backend: None,
+            quota: Arc::new(Quota::new()),
+            transport: None,
+            resolve: Vec::new(),
+            max_response_bytes: None,
+            plot_defaults: Vec::new(),
+            builder: None,
+        }
+    }
+
+    /// Create a client sending `apikey` with every query, sending
+    /// requests through `proxy`.
+    ///
+    /// Without a proxy, clients already use the proxies set in the
+    /// `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` environment variables.
+    /// SOCKS proxies need the `socks` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example() -> Result<(), omdb::Error> {
+    /// let proxy = reqwest::Proxy::all("http://proxy.example.com:8080")?;
+    /// let client = omdb::Client::with_proxy("1a2b3c4d", proxy)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_proxy<S: ToString>(apikey: S, proxy: reqwest::Proxy) -> Result<Client, Error> {
+        let options = HttpOptions::new(Some(proxy));
+
+        Ok(Client {
+            builder: Some(options.clone()),
+            ..Client::with_client(apikey, options.build()?)
+        })
+    }
+
+    /// Whether to ask for gzip or brotli compressed responses, which
+    /// are decompressed as they're read. On by default.
+    ///
+    /// Compression uses reqwest's `gzip` and `brotli` features. A
+    /// client given to `with_client` keeps its own setting, so this
+    /// has no effect on it.
+    pub fn compression(&mut self, enabled: bool) -> &mut Client {
+        self.rebuild(|options| options.compression = enabled)
+    }
+
+    /// Speak HTTP/2 without first negotiating it. Off by default.
+    ///
+    /// Only turn this on for servers known to support HTTP/2, such as
+    /// a proxy in front of OMDb. Like `compression`, this has no effect
+    /// on a client given to `with_client`.
+    pub fn http2_prior_knowledge(&mut self, enabled: bool) -> &mut Client {
+        self.rebuild(|options| options.http2_prior_knowledge = enabled)
+    }
+
+    /// Send TCP keep-alive probes every `interval` on idle connections.
+    /// Off by default.
+    ///
+    /// See `http2_prior_knowledge` for clients given to `with_client`.
+    pub fn tcp_keepalive(&mut self, interval: Duration) -> &mut Client {
+        self.rebuild(|options| options.tcp_keepalive = Some(interval))
+    }
+
+    /// Close pooled connections that have been idle for `timeout`.
+    /// Defaults to reqwest's 90 seconds.
+    ///
+    /// See `http2_prior_knowledge` for clients given to `with_client`.
+    pub fn pool_idle_timeout(&mut self, timeout: Duration) -> &mut Client {
+        self.rebuild(|options| options.pool_idle_timeout = Some(timeout))
+    }
+
+    /// Send this client's queries to `base_url` instead of OMDb.
+    ///
+    /// See `FindQuery::base_url`.
+    pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut Client {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Send this client's requests through `transport` instead of
+    /// `reqwest`.
+    ///
+    /// See `Transport`.
+    pub fn transport(&mut self, transport: Arc<dyn Transport>) -> &mut Client {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Send requests for `host` to `addr`, including its port, instead
+    /// of looking the host up.
+    ///
+    /// The `Host` header still names `host`, so tests can send the
+    /// production URL to a local server. Only
+    /// plain `http` works this way, since TLS would check the server's
+    /// certificate against the address. Requests sent through a
+    /// `Transport` aren't affected.
+    pub fn resolve(&mut self, host: &str, addr: SocketAddr) -> &mut Client {
+        self.resolve
+            .retain(|(name, _)| !name.eq_ignore_ascii_case(host));
+        self.resolve.push((host.to_owned(), addr));
+        self
+    }
+
+    /// Fail with `Error::Other` rather than read more than `max` bytes of
+    /// a response body, in case a misbehaving proxy sends a huge one.
+    ///
+    /// Response bodies aren't limited by default.
+    pub fn max_response_bytes(&mut self, max: usize) -> &mut Client {
+        self.max_response_bytes = Some(max);
+        self
+    }
+
+    /// Request version `api_version` of OMDb's API for this client's
+    /// queries.
+    ///
+    /// See `FindQuery::api_version`.
+    pub fn api_version<S: Into<String>>(&mut self, api_version: S) -> &mut Client {
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Send `user_agent` as the `User-Agent` header of this client's
+    /// queries.
+    ///
+    /// See `FindQuery::user_agent`.
+    pub fn user_agent<S: Into<String>>(&mut self, user_agent: S) -> &mut Client {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Ask for `plot` on lookups of `kind` that don't set a plot length.
+    ///
+    /// Lookups only have a kind if one is set with `FindQuery::kind`.
+    /// For example, series often need only a short plot in a list while
+    /// movies need the full one. `FindQuery::plot` always takes
+    /// precedence.
+    pub fn plot_for(&mut self, kind: Kind, plot: Plot) -> &mut Client {
+        self.plot_defaults
+            .retain(|(default_kind, _)| *default_kind != kind);
+        self.plot_defaults.push((kind, plot));
+        self
+    }
+
+    /// Send at most `requests_per_second` requests a second, across
+    /// every query from this client and its clones.
+    ///
+    /// Requests over the limit wait their turn rather than failing.
+    /// Bursts of up to a second's worth of requests go out at once.
+    /// A rate that isn't positive turns rate limiting off.
+    ///
+    /// OMDb's free keys allow 1,000 requests a day, which is about
+    /// `0.0116` a second.
+    pub fn with_rate_limit(&mut self, requests_per_second: f64) -> &mut Client {
+        self.limiter = if requests_per_second > 0.0 {
+            Some(Arc::new(RateLimiter::new(requests_per_second)))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Cache up to `capacity` responses in memory, each for `ttl`,
+    /// across every query from this client and its clones.
+    ///
+    /// Responses are cached by their parameters other than the API key,
+    /// such as the title, kind, year, plot and page. A cached response
+    /// is returned without sending a request. Once the cache is full,
+    /// the least recently used response is evicted. Errors, including
+    /// OMDb's `Movie not found!`, are never cached, and
+    /// `FindQuery::get_with_raw` always sends a request.
+    pub fn with_cache(&mut self, capacity: usize, ttl: Duration) -> &mut Client {
+        self.cache = Some(Arc::new(Cache::new(capacity, ttl)));
+        self
+    }
+
+    /// Look up movies in `backend` before sending a request, and store
+    /// the movies fetched in it, across every query from this client
+    /// and its clones.
+    ///
+    /// Movies are looked for in the cache set with `with_cache` first.
+    /// Searches aren't stored in `backend`, and `clear_cache` leaves it
+    /// as it is.
+    pub fn with_cache_backend(&mut self, backend: Arc<dyn CacheBackend>) -> &mut Client {
+        self.cache_backend = Some(backend);
+        self
+    }
+
+    /// Remove every cached response.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = self.cache.as_ref() {
+            cache.clear();
+        }
+    }
+
+    /// The number of successful requests sent by this client and its
+    /// clones.
+    ///
+    /// OMDb doesn't report how much of its quota is left, so this is
+    /// counted locally. Requests are counted as they're sent, and
+    /// uncounted if they fail. Cached responses aren't counted. With a
+    /// limit set, this is the count since the current window started.
+    pub fn request_count(&self) -> u64 {
+        self.quota.count()
+    }
+
+    /// Reset `request_count` to zero.
+    pub fn reset_count(&self) {
+        self.quota.reset()
+    }
+
+    /// Fail requests with `ApiError::LimitReached`, without sending
+    /// them, once `limit` requests have been sent in a day.
+    ///
+    /// See `with_request_limit`.
+    pub fn with_daily_limit(&mut self, limit: u64) -> &mut Client {
+        self.with_request_limit(limit, Duration::from_secs(24 * 60 * 60))
+    }
+
+    /// Fail requests with `ApiError::LimitReached`, without sending
+    /// them, once `limit` requests have been sent in the current
+    /// `window`.
+    ///
+    /// The first window starts now, and the count resets to zero as each
+    /// window ends. The limit is shared with this client's clones.
+    pub fn with_request_limit(&mut self, limit: u64, window: Duration) -> &mut Client {
+        self.quota.set_limit(limit, window);
+        self
+    }
+
+    /// Starts a new `FindQuery` with an imdb_id.
+    pub fn imdb_id<S: Into<String>>(&self, imdb_id: S) -> FindQuery {
+        self.find_query(query::imdb_id(imdb_id))
+    }
+
+    /// Starts a new `BatchQuery` looking up each of `ids` by IMDb ID.
+    pub fn imdb_ids<I>(&self, ids: I) -> BatchQuery
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut query = query::imdb_ids(ids);
+        query.apikey(&self.apikey);
+        if let Some(user_agent) = self.user_agent.as_ref() {
+            query.user_agent(user_agent);
+        }
+        self.configure(query.http_mut());
+        query
+    }
+
+    /// Starts a new `FindQuery` with a title.
+    pub fn title<S: Into<String>>(&self, title: S) -> FindQuery {
+        self.find_query(query::title(title))
+    }
+
+    /// Starts a new `FindQuery` with an IMDb ID or a title, like `omdb::find`.
+    pub fn find<S: Into<String>>(&self, id_or_title: S) -> FindQuery {
+        self.find_query(query::find(id_or_title))
+    }
+
+    /// Starts a new `SearchQuery`.
+    pub fn search<S: Into<String>>(&self, search: S) -> SearchQuery {
+        let mut query = query::search(search);
+        query.apikey(&self.apikey);
+        if let Some(user_agent) = self.user_agent.as_ref() {
+            query.user_agent(user_agent);
+        }
+        self.configure(query.http_mut());
+        query
+    }
+
+    /// Download `movie`'s poster, reusing this client's connections.
+    ///
+    /// See `Movie::download_poster`.
+    pub async fn download_poster(&self, movie: &Movie) -> Result<PosterImage, Error> {
+        movie.download_poster(&self.http).await
+    }
+
+    /// Changes the options this client built its `reqwest::Client`
+    /// with, and builds it again.
+    fn rebuild<F>(&mut self, change: F) -> &mut Client
+    where
+        F: FnOnce(&mut HttpOptions),
+    {
+        if let Some(options) = self.builder.as_mut() {
+            change(options);
+            // These options built a client before, so this only fails
+            // if something like TLS setup has broken since
+            if let Ok(http) = options.build() {
+                self.http = http;
+            }
+        }
+        self
+    }
+
+    /// Shares this client's connections, limits, cache and settings
+    /// with a query's `http`.
+    fn configure(&self, http: &mut Http) {
+        http.client = Some(self.http.clone());
+        http.base_url = self.base_url.clone();
+        http.api_version = self.api_version.clone();
+        http.limiter = self.limiter.clone();
+        http.cache = self.cache.clone();
+        http.cache_backend = self.cache_backend.clone();
+        http.quota = Some(self.quota.clone());
+        http.transport = self.transport.clone();
+        http.resolve = self.resolve.clone();
+        http.max_response_bytes = self.max_response_bytes;
+        http.plot_defaults = self.plot_defaults.clone();
+    }
+
+    fn find_query(&self, mut query: FindQuery) -> FindQuery {
+        query.apikey(&self.apikey);
+        if let Some(user_agent) = self.user_agent.as_ref() {
+            query.user_agent(user_agent);
+        }
+        self.configure(query.http_mut());
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiError;
+
+    #[test]
+    fn queries_have_apikey() {
+        let client = Client::new("1a2b3c4d");
+        let apikey = ("apikey", String::from("1a2b3c4d"));
+
+        assert!(client.title("The Wizard of Oz").params().contains(&apikey));
+        assert!(client.imdb_id("tt0032138").params().contains(&apikey));
+        assert!(client.find("tt0032138").params().contains(&apikey));
+        assert!(client.search("batman").params().contains(&apikey));
+    }
+
+    #[test]
+    fn queries_match_free_functions() {
+        let client = Client::new("1a2b3c4d");
+
+        let mut query = query::title("The Wizard of Oz");
+        query.apikey("1a2b3c4d");
+        assert_eq!(client.title("The Wizard of Oz"), query);
+    }
+
+    #[tokio::test]
+    async fn proxy() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let proxy = reqwest::Proxy::http(&url).unwrap();
+        let mut client = Client::with_proxy("1a2b3c4d", proxy).unwrap();
+        client.base_url("http://omdb.invalid");
+
+        let movie = client.imdb_id("tt0032138").get().await.unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET http://omdb.invalid/?"));
+    }
+
+    #[tokio::test]
+    async fn cache() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let mut client = Client::new("1a2b3c4d");
+        client
+            .base_url(&url)
+            .with_cache(10, Duration::from_secs(60));
+
+        // The second lookup is cached, even with another API key
+        client.imdb_id("tt0032138").get().await.unwrap();
+        let movie = client
+            .imdb_id("tt0032138")
+            .apikey("5e6f7a8b")
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        client.clear_cache();
+        client.imdb_id("tt0032138").get().await.unwrap();
+
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn cache_backend() {
+        use std::collections::HashMap;
+        use std::sync::Mutex;
+
+        #[derive(Debug, Default)]
+        struct Memory(Mutex<HashMap<String, Movie>>);
+
+        impl CacheBackend for Memory {
+            fn get(&self, key: &str) -> Option<Movie> {
+                self.0.lock().unwrap().get(key).cloned()
+            }
+
+            fn put(&self, key: &str, movie: Movie) {
+                self.0.lock().unwrap().insert(key.to_owned(), movie);
+            }
+        }
+
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+        let backend = Arc::new(Memory::default());
+
+        let mut client = Client::new("1a2b3c4d");
+        client.base_url(&url).with_cache_backend(backend.clone());
+        client.imdb_id("tt0032138").get().await.unwrap();
+
+        // Another client sharing the backend finds the movie there
+        let mut other = Client::new("5e6f7a8b");
+        other.base_url(&url).with_cache_backend(backend.clone());
+        let movie = other.imdb_id("tt0032138").get().await.unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        assert_eq!(server.join().unwrap().len(), 1);
+        let keys: Vec<_> = backend.0.lock().unwrap().keys().cloned().collect();
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].ends_with("&v=1&i=tt0032138"));
+    }
+
+    #[tokio::test]
+    async fn daily_limit() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let mut client = Client::new("1a2b3c4d");
+        client.base_url(&url).with_daily_limit(1);
+
+        client.imdb_id("tt0032138").get().await.unwrap();
+        assert_eq!(client.request_count(), 1);
+
+        match client.imdb_id("tt0032138").get().await {
+            Err(Error::Api(ApiError::LimitReached)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(server.join().unwrap().len(), 1);
+
+        client.reset_count();
+        assert_eq!(client.request_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_limit() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let mut client = Client::new("1a2b3c4d");
+        client.base_url(&url).with_daily_limit(1);
+
+        // Every lookup is in flight at once
+        let results = client
+            .imdb_ids(vec!["tt0032138", "tt0016544", "tt0000702", "tt0017136"])
+            .concurrency(4)
+            .get()
+            .await;
+
+        let limited = results
+            .iter()
+            .filter(|result| matches!(result, Err(Error::Api(ApiError::LimitReached))))
+            .count();
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert_eq!(limited, 3);
+        assert_eq!(server.join().unwrap().len(), 1);
+        assert_eq!(client.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn compression() {
+        let body = r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#;
+        let (url, server) = crate::mock::serve(vec![body, body]);
+
+        let mut client = Client::new("1a2b3c4d");
+        client.base_url(&url);
+        client.imdb_id("tt0032138").get().await.unwrap();
+        client.compression(false);
+        client.imdb_id("tt0032138").get().await.unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("accept-encoding: gzip, br"));
+        assert!(!requests[1].to_lowercase().contains("accept-encoding"));
+    }
+
+    #[tokio::test]
+    async fn connection_options() {
+        let body = r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#;
+        let (url, server) = crate::mock::serve(vec![body, body]);
+
+        let mut client = Client::new("1a2b3c4d");
+        client
+            .base_url(&url)
+            .tcp_keepalive(Duration::from_secs(30))
+            .pool_idle_timeout(Duration::from_secs(10));
+        client.imdb_id("tt0032138").get().await.unwrap();
+
+        // The mock server only speaks HTTP/1.1
+        client.http2_prior_knowledge(true);
+        assert!(client.imdb_id("tt0032138").get().await.is_err());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /?"));
+        assert!(requests[1].starts_with("PRI * HTTP/2.0"));
+    }
+
+    #[test]
+    fn plot_for() {
+        let mut client = Client::new("1a2b3c4d");
+        client
+            .plot_for(Kind::Movie, Plot::Short)
+            .plot_for(Kind::Movie, Plot::Full)
+            .plot_for(Kind::Series, Plot::Short);
+        let plot = |query: &FindQuery| {
+            query
+                .params()
+                .into_iter()
+                .find(|(name, _)| *name == "plot")
+                .map(|(_, value)| value)
+        };
+
+        assert_eq!(
+            plot(client.title("The Wizard of Oz").kind(Kind::Movie)).as_deref(),
+            Some("full")
+        );
+        assert_eq!(
+            plot(client.title("Silicon Valley").kind(Kind::Series)).as_deref(),
+            Some("short")
+        );
+        assert_eq!(plot(&client.title("The Wizard of Oz")), None);
+        assert_eq!(
+            plot(
+                client
+                    .title("The Wizard of Oz")
+                    .kind(Kind::Movie)
+                    .plot(Plot::Short)
+            )
+            .as_deref(),
+            Some("short")
+        );
+    }
+
+    #[tokio::test]
+    async fn max_response_bytes() {
+        let plot = "A".repeat(4096);
+        let body = format!(
+            r#"{{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138","Plot":"{}"}}"#,
+            plot
+        );
+        let (url, server) = crate::mock::serve(vec![&body, &body]);
+
+        let mut client = Client::new("1a2b3c4d");
+        client.base_url(&url).max_response_bytes(1024);
+        match client.imdb_id("tt0032138").get().await {
+            Err(Error::Other(desc)) => assert_eq!(desc, "response too large"),
+            other => panic!("expected a too large response, got {:?}", other),
+        }
+
+        client.max_response_bytes(8192);
+        let movie = client.imdb_id("tt0032138").get().await.unwrap();
+        assert_eq!(movie.plot, plot);
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolve() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+        let addr = url.trim_start_matches("http://").parse().unwrap();
+
+        let mut client = Client::new("1a2b3c4d");
+        client
+            .base_url("http://omdbapi.com")
+            .resolve("omdbapi.com", addr);
+
+        let movie = client.imdb_id("tt0032138").get().await.unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        let requests = server.join().unwrap();
+        let head = requests[0].to_lowercase();
+        assert!(head.contains("host: omdbapi.com\r\n"));
+        assert_eq!(head.matches("host:").count(), 1);
+    }
+
+    #[test]
+    fn base_url() {
+        let mut client = Client::new("1a2b3c4d");
+        client.base_url("http://localhost:8080");
+
+        let plan = client.search("batman").plan().unwrap();
+        assert_eq!(plan.base_url, "http://localhost:8080");
+        assert!(plan.url.starts_with("http://localhost:8080/?"));
+    }
+
+    #[test]
+    fn api_version() {
+        let mut client = Client::new("1a2b3c4d");
+        assert!(client
+            .title("Oz")
+            .plan()
+            .unwrap()
+            .url
+            .contains("v=1&r=json"));
+
+        client.api_version("2");
+        assert!(client
+            .title("Oz")
+            .plan()
+            .unwrap()
+            .url
+            .contains("v=2&r=json"));
+        assert!(client
+            .search("Oz")
+            .plan()
+            .unwrap()
+            .url
+            .contains("v=2&r=json"));
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::cmp::Ordering;
+
+use crate::{Episode, Movie};
+
+impl Movie {
+    /// The date the movie was released.
+    ///
+    /// Returns `None` if the release date is unknown or can't be parsed.
+    pub fn released_date(&self) -> Option<NaiveDate> {
+        parse_date(&self.released)
+    }
+
+    /// The day of the week the movie was released on.
+    ///
+    /// Returns `None` if the release date is unknown or can't be parsed.
+    pub fn released_weekday(&self) -> Option<Weekday> {
+        parse_date(&self.released).map(|date| date.weekday())
+    }
+
+    /// Compare movies by their release dates, earliest first.
+    ///
+    /// Movies whose release date is unknown or can't be parsed come
+    /// after every movie with a known date.
+    pub fn cmp_by_release(&self, other: &Movie) -> Ordering {
+        cmp_dates(parse_date(&self.released), parse_date(&other.released))
+    }
+}
+
+impl Episode {
+    /// The date the episode aired.
+    ///
+    /// Returns `None` if the date is unknown or can't be parsed.
+    pub fn released_date(&self) -> Option<NaiveDate> {
+        parse_date(&self.released)
+    }
+}
+
+/// Orders dates earliest first, with unknown dates last.
+fn cmp_dates(a: Option<NaiveDate>, b: Option<NaiveDate>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Parses a date from OMDb, like `25 Aug 1939`.
+///
+/// Season episode lists use ISO dates like `2011-04-17` instead.
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    let date = date.trim();
+
+    NaiveDate::parse_from_str(date, "%d %b %Y")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_weekday() {
+        assert_eq!(
+            parse_date("25 Aug 1939").map(|date| date.weekday()),
+            Some(Weekday::Fri)
+        );
+        assert_eq!(parse_date("N/A"), None);
+    }
+
+    #[test]
+    fn released_date() {
+        assert_eq!(
+            parse_date("25 Aug 1939"),
+            NaiveDate::from_ymd_opt(1939, 8, 25)
+        );
+        assert_eq!(
+            parse_date("2011-04-17"),
+            NaiveDate::from_ymd_opt(2011, 4, 17)
+        );
+        assert_eq!(parse_date(""), None);
+        assert_eq!(parse_date("Aug 1939"), None);
+    }
+
+    #[test]
+    fn release_order() {
+        let mut released = vec!["15 Dec 1939", "N/A", "25 Aug 1939", "01 Apr 1938"];
+        released.sort_by(|a, b| cmp_dates(parse_date(a), parse_date(b)));
+
+        assert_eq!(
+            released,
+            vec!["01 Apr 1938", "25 Aug 1939", "15 Dec 1939", "N/A"]
+        );
+    }
+}
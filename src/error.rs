@@ -1,42 +1,296 @@
 use std::error::Error as StdError;
-use std::fmt;
+use std::{fmt, io};
 
 use reqwest::StatusCode;
 
+/// An error from this crate.
+///
+/// More variants may be added in minor releases, so matches on an
+/// `Error` need a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// An error originating from Reqwest.
     Http(reqwest::Error),
+    /// JSON that couldn't be decoded.
+    Json(serde_json::Error),
+    /// A response from OMDb that couldn't be decoded, such as a
+    /// truncated body, one with renamed fields, or an HTML error page.
+    Decode {
+        /// The start of the response body.
+        body: String,
+        source: serde_json::Error,
+    },
     /// An unexpected HTTP status code.
     Status(StatusCode),
     /// An error from OMDb.
-    Api(String),
+    Api(ApiError),
+    /// OMDb rejected the API key with an HTML page instead of JSON.
+    InvalidApiKey,
+    /// A query had no API key, and `OMDB_APIKEY` wasn't set.
+    MissingApiKey,
+    /// An IMDb ID that isn't `tt` followed by at least 7 digits.
+    InvalidImdbId(String),
 
     Other(&'static str),
 }
 
+/// An error message from OMDb.
+///
+/// OMDb's common messages have their own variants, and any other
+/// message is kept as-is in `Other`. `MultipleResults` is the only
+/// one detected by this crate.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ApiError {
+    /// `Movie not found!`
+    NotFound,
+    /// `Series or Episode not found!`
+    SeriesOrEpisodeNotFound,
+    /// `Incorrect IMDb ID.`
+    IncorrectImdbId,
+    /// `No API key provided.`
+    NoApiKey,
+    /// `Invalid API key!`
+    InvalidApiKey,
+    /// `Request limit reached!`
+    LimitReached,
+    /// `Too many results.`
+    TooManyResults,
+    /// More than one title matched a `FindQuery` with `exact` set.
+    ///
+    /// This comes from the client rather than OMDb.
+    MultipleResults,
+    /// Any other message.
+    Other(String),
+}
+
+impl ApiError {
+    /// The `ApiError` for one of OMDb's error messages.
+    pub fn from_message(message: &str) -> ApiError {
+        match message.trim() {
+            "Movie not found!" => ApiError::NotFound,
+            "Series or Episode not found!" => ApiError::SeriesOrEpisodeNotFound,
+            "Incorrect IMDb ID." => ApiError::IncorrectImdbId,
+            "No API key provided." => ApiError::NoApiKey,
+            "Invalid API key!" => ApiError::InvalidApiKey,
+            "Request limit reached!" => ApiError::LimitReached,
+            "Too many results." => ApiError::TooManyResults,
+            _ => ApiError::Other(message.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ApiError::NotFound => "Movie not found!".fmt(f),
+            ApiError::SeriesOrEpisodeNotFound => "Series or Episode not found!".fmt(f),
+            ApiError::IncorrectImdbId => "Incorrect IMDb ID.".fmt(f),
+            ApiError::NoApiKey => "No API key provided.".fmt(f),
+            ApiError::InvalidApiKey => "Invalid API key!".fmt(f),
+            ApiError::LimitReached => "Request limit reached!".fmt(f),
+            ApiError::TooManyResults => "Too many results.".fmt(f),
+            ApiError::MultipleResults => "Multiple results.".fmt(f),
+            ApiError::Other(ref message) => message.fmt(f),
+        }
+    }
+}
+
+/// The most of a response body kept in an `Error::Decode`, in characters.
+const BODY_SNIPPET: usize = 200;
+
+impl Error {
+    /// A `Decode` error for `body`, keeping only its start.
+    pub(crate) fn decode(body: &str, source: serde_json::Error) -> Error {
+        let mut snippet: String = body.chars().take(BODY_SNIPPET).collect();
+        if snippet.len() < body.len() {
+            snippet.push('…');
+        }
+
+        Error::Decode {
+            body: snippet,
+            source,
+        }
+    }
+
+    /// An `Api` error for one of OMDb's error messages.
+    pub(crate) fn api(message: &str) -> Error {
+        Error::Api(ApiError::from_message(message))
+    }
+
+    /// The underlying reqwest error, if this is an `Http` error.
+    pub fn as_reqwest(&self) -> Option<&reqwest::Error> {
+        match *self {
+            Error::Http(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
             Error::Http(ref err) => Some(err),
+            Error::Json(ref err) => Some(err),
+            Error::Decode { ref source, .. } => Some(source),
             _ => None,
         }
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Error {
         Error::Http(err)
     }
 }
 
+impl From<Error> for io::Error {
+    /// Wrap the error as `io::ErrorKind::Other`, keeping its message
+    /// and source chain.
+    fn from(err: Error) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::Http(ref err) => err.fmt(f),
-            Error::Status(status) => status.canonical_reason().unwrap_or("Unknown status").fmt(f),
-            Error::Api(ref desc) => desc.fmt(f),
+            Error::Json(ref err) => err.fmt(f),
+            Error::Decode {
+                ref body,
+                ref source,
+            } => write!(f, "{} in response `{}`", source, body),
+            Error::Status(status) => write!(f, "Unexpected HTTP status {}", status),
+            Error::Api(ref err) => err.fmt(f),
+            Error::InvalidApiKey => "API key rejected by OMDb".fmt(f),
+            Error::MissingApiKey => "Missing API key".fmt(f),
+            Error::InvalidImdbId(ref id) => write!(f, "Invalid IMDb ID: {}", id),
             Error::Other(desc) => desc.fmt(f),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn as_reqwest() {
+        let err: Error = reqwest::get("not a url").await.unwrap_err().into();
+        assert!(err.as_reqwest().unwrap().is_builder());
+
+        assert!(Error::api("Movie not found!").as_reqwest().is_none());
+    }
+
+    #[test]
+    fn into_io() {
+        let errors = vec![
+            serde_json::from_str::<u8>("{").unwrap_err().into(),
+            Error::decode("{", serde_json::from_str::<u8>("{").unwrap_err()),
+            Error::Status(StatusCode::NOT_FOUND),
+            Error::api("Movie not found!"),
+            Error::InvalidApiKey,
+            Error::MissingApiKey,
+            Error::InvalidImdbId(String::from("foo")),
+            Error::Other("malformed API key"),
+        ];
+
+        for err in errors {
+            let message = err.to_string();
+            let has_source = err.source().is_some();
+
+            let io_err = io::Error::from(err);
+            assert_eq!(io_err.kind(), io::ErrorKind::Other);
+            assert_eq!(io_err.to_string(), message);
+
+            let inner = io_err.get_ref().unwrap();
+            assert_eq!(inner.source().is_some(), has_source);
+        }
+    }
+
+    #[test]
+    fn source_chain() {
+        let json = serde_json::from_str::<u8>("{").unwrap_err();
+        let message = json.to_string();
+        let err = Error::decode("{", json);
+
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), message);
+        assert!(source.downcast_ref::<serde_json::Error>().is_some());
+        assert!(source.source().is_none());
+
+        let leaves = vec![
+            Error::Status(StatusCode::NOT_FOUND),
+            Error::api("Movie not found!"),
+            Error::api("Invalid API key!"),
+            Error::InvalidApiKey,
+            Error::MissingApiKey,
+            Error::InvalidImdbId(String::from("foo")),
+            Error::Other("malformed API key"),
+        ];
+
+        let mut messages: Vec<_> = leaves.iter().map(Error::to_string).collect();
+        assert_eq!(messages[0], "Unexpected HTTP status 404 Not Found");
+        messages.push(err.to_string());
+        messages.sort();
+        messages.dedup();
+        assert_eq!(messages.len(), leaves.len() + 1);
+
+        for leaf in leaves {
+            assert!(leaf.source().is_none(), "{:?} has a source", leaf);
+        }
+    }
+
+    #[test]
+    fn decode_snippet() {
+        let body = format!("{{\"Title\":\"{}", "a".repeat(500));
+        let err = Error::decode(&body, serde_json::from_str::<u8>(&body).unwrap_err());
+
+        match err {
+            Error::Decode { ref body, .. } => {
+                assert!(body.starts_with("{\"Title\":\"aaa"));
+                assert!(body.ends_with('…'));
+                assert_eq!(body.chars().count(), BODY_SNIPPET + 1);
+            }
+            ref other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(err.to_string().contains("in response `{\"Title\""));
+    }
+
+    #[test]
+    fn api_errors() {
+        let errors = [
+            ("Movie not found!", ApiError::NotFound),
+            (
+                "Series or Episode not found!",
+                ApiError::SeriesOrEpisodeNotFound,
+            ),
+            ("Incorrect IMDb ID.", ApiError::IncorrectImdbId),
+            ("No API key provided.", ApiError::NoApiKey),
+            ("Invalid API key!", ApiError::InvalidApiKey),
+            ("Request limit reached!", ApiError::LimitReached),
+            ("Too many results.", ApiError::TooManyResults),
+            (
+                "Something went wrong.",
+                ApiError::Other(String::from("Something went wrong.")),
+            ),
+        ];
+
+        for (message, expected) in errors.iter() {
+            let err = Error::api(message);
+            assert_eq!(err.to_string(), *message);
+
+            match err {
+                Error::Api(api) => assert_eq!(api, *expected),
+                other => panic!("unexpected error: {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{Error, SearchResults};
+
+impl SearchResults {
+    /// Export the results as CSV.
+    ///
+    /// There's a header row followed by one row per result, with the
+    /// columns `title`, `year`, `imdb_id`, `kind`, and `poster`.
+    pub fn to_csv(&self) -> Result<String, Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        writer
+            .write_record(["title", "year", "imdb_id", "kind", "poster"])
+            .map_err(|_| Error::Other("failed to write CSV"))?;
+
+        for movie in self.results.iter() {
+            let kind: &str = movie.kind.into();
+            writer
+                .write_record([
+                    movie.title.as_str(),
+                    movie.year.as_str(),
+                    movie.imdb_id.as_str(),
+                    kind,
+                    movie.poster.as_str(),
+                ])
+                .map_err(|_| Error::Other("failed to write CSV"))?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|_| Error::Other("failed to write CSV"))?;
+
+        String::from_utf8(bytes).map_err(|_| Error::Other("failed to write CSV"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Kind, SearchResults, SearchResultsMovie};
+
+    #[test]
+    fn to_csv() {
+        let results = SearchResults {
+            results: vec![
+                SearchResultsMovie {
+                    title: String::from("Batman, the \"Movie\""),
+                    year: String::from("1966"),
+                    imdb_id: String::from("tt0060153"),
+                    poster: String::from("N/A"),
+                    kind: Kind::Movie,
+                },
+                SearchResultsMovie {
+                    title: String::from("Batman"),
+                    year: String::from("1966–1968"),
+                    imdb_id: String::from("tt0059968"),
+                    poster: String::from("N/A"),
+                    kind: Kind::Series,
+                },
+            ],
+            total_results: 2,
+            page: 1,
+        };
+
+        let csv = results.to_csv().unwrap();
+        let rows: Vec<csv::StringRecord> = csv::Reader::from_reader(csv.as_bytes())
+            .records()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(&rows[0][0], "Batman, the \"Movie\"");
+        assert_eq!(&rows[1][1], "1966–1968");
+        assert_eq!(&rows[1][3], "series");
+    }
+}
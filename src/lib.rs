@@ -1,20 +1,69 @@
 //! OMDb API for Rust
 //!
 //! [Github Repo](https://github.com/aldrio/omdb-rs)
+//!
+//! Requests are sent to `https://omdbapi.com`, unless a query has its own
+//! `base_url` or the `OMDB_BASE_URL` environment variable is set, in which
+//! case they're sent there instead.
+//!
+//! Queries can be started with the free functions, such as `omdb::title`,
+//! or from a `Client`, which fills in the API key and reuses connections.
+//! Queries without an API key use the `OMDB_APIKEY` environment variable.
+//!
+//! Every request is async. With the `blocking` feature, queries also
+//! have blocking versions of their methods, such as
+//! `FindQuery::get_blocking`. With the `tracing` feature, requests and
+//! OMDb's errors are logged with `tracing`, never including the API key.
+//!
+//! Responses are requested gzip or brotli compressed, using reqwest's
+//! `gzip` and `brotli` features. `Client::compression` turns this off.
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::Duration;
+
+mod client;
+pub use client::Client;
 
 mod error;
-pub use error::Error;
+pub use error::{ApiError, Error};
+
+mod poster;
+pub use poster::PosterImage;
+
+mod transport;
+pub use transport::{Transport, TransportFuture};
+
+#[cfg(feature = "csv")]
+mod export;
+
+#[cfg(feature = "chrono")]
+mod date;
+
+#[cfg(feature = "blocking")]
+mod blocking;
+
+#[cfg(test)]
+mod mock;
 
 pub mod query;
+pub use query::find;
 pub use query::imdb_id;
+pub use query::imdb_ids;
 pub use query::search;
 pub use query::title;
+pub use query::CacheBackend;
 
 /// A movie, series, episode, or game from OMDb.
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Values OMDb doesn't know, which it sends as `N/A`, are empty.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Movie {
     pub title: String,
+    /// The title in its original language, when OMDb provides one.
+    pub original_title: Option<String>,
     pub year: String,
     pub rated: String,
     pub released: String,
@@ -33,19 +82,873 @@ pub struct Movie {
     pub imdb_votes: String,
     pub imdb_id: String,
     pub kind: Kind,
+    /// The DVD release date, like `06 Dec 2005`.
+    #[serde(default)]
+    pub dvd: String,
+    /// The US box office gross, like `$24,668,669`.
+    #[serde(default)]
+    pub box_office: String,
+    #[serde(default)]
+    pub production: String,
+    #[serde(default)]
+    pub website: String,
+    /// Ratings from IMDb, Rotten Tomatoes, Metacritic, etc.
+    #[serde(default)]
+    pub ratings: Vec<Rating>,
+    /// The number of seasons, for a series.
+    #[serde(default)]
+    pub total_seasons: String,
+    /// A season's episodes, when fetched with `FindQuery::season`.
+    #[serde(default)]
+    pub episodes: Vec<Episode>,
+}
+
+impl Movie {
+    /// The IMDb rating mapped onto a scale of `max_stars` stars.
+    ///
+    /// Returns `None` if the movie has no valid IMDb rating.
+    pub fn imdb_rating_stars(&self, max_stars: u8) -> Option<f32> {
+        let rating = self.imdb_rating.parse::<f32>().ok()?;
+
+        Some(rating / 10.0 * f32::from(max_stars))
+    }
+
+    /// The quality tier of the movie's IMDb rating.
+    ///
+    /// Returns `None` if the movie has no valid IMDb rating.
+    pub fn imdb_rating_bucket(&self) -> Option<RatingBucket> {
+        let rating = self.imdb_rating.parse::<f32>().ok()?;
+
+        Some(RatingBucket::from_rating(rating))
+    }
+
+    /// The popularity tier of the movie, by its number of IMDb votes.
+    ///
+    /// Returns `None` if the number of votes is unknown.
+    pub fn votes_tier(&self) -> Option<VotesTier> {
+        let votes = parse_votes(&self.imdb_votes)?;
+
+        Some(VotesTier::from_votes(votes))
+    }
+
+    /// The years the movie was released over, as `(start, end)`.
+    ///
+    /// A single year like `1939` gives `(1939, Some(1939))`, a range like
+    /// `2011–2019` gives `(2011, Some(2019))`, and an ongoing series like
+    /// `2014–` gives `(2014, None)`.
+    pub fn year_range(&self) -> Option<(u16, Option<u16>)> {
+        parse_year_range(&self.year)
+    }
+
+    /// The search result equivalent of this movie.
+    ///
+    /// Only the fields `SearchResultsMovie` has are kept.
+    pub fn to_minimal(&self) -> SearchResultsMovie {
+        SearchResultsMovie {
+            title: self.title.clone(),
+            year: self.year.clone(),
+            imdb_id: self.imdb_id.clone(),
+            poster: self.poster.clone(),
+            kind: self.kind,
+        }
+    }
+
+    /// A copy of this movie without its `episodes`.
+    ///
+    /// A season's episode list can be long, so this saves memory when
+    /// keeping many series around just for their details.
+    pub fn clone_without_episodes(&self) -> Movie {
+        Movie {
+            episodes: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// The number of episodes listed for a season.
+    pub fn episode_count(&self) -> usize {
+        self.episodes.len()
+    }
+
+    /// Whether a season's episodes are numbered `1` to `N` without gaps,
+    /// in any order.
+    ///
+    /// OMDb occasionally leaves episodes out of a season, so this is a
+    /// cheap check that one is complete. A movie without episodes, or
+    /// with an episode number that isn't a number, isn't contiguous.
+    pub fn has_contiguous_episodes(&self) -> bool {
+        let mut numbers = Vec::with_capacity(self.episodes.len());
+        for episode in &self.episodes {
+            match episode.episode.trim().parse::<usize>() {
+                Ok(number) => numbers.push(number),
+                Err(_) => return false,
+            }
+        }
+        numbers.sort_unstable();
+
+        !numbers.is_empty() && numbers.iter().copied().eq(1..=numbers.len())
+    }
+
+    /// Roughly how long a season takes to watch.
+    ///
+    /// OMDb doesn't give episodes their own runtimes, so the series'
+    /// `runtime` is taken as each episode's. Returns `None` without
+    /// episodes or a known runtime.
+    pub fn estimated_season_runtime(&self) -> Option<Duration> {
+        if self.episodes.is_empty() {
+            return None;
+        }
+        let minutes = u64::from(self.runtime_minutes()?);
+
+        Some(Duration::from_secs(
+            minutes * 60 * self.episodes.len() as u64,
+        ))
+    }
+
+    /// A view of the movie where unknown values are `None`.
+    ///
+    /// Fields that are empty or `N/A` become `None`, and the rating,
+    /// votes, metascore, and runtime are parsed into numbers.
+    pub fn as_optional(&self) -> MovieView {
+        MovieView {
+            title: known(&self.title),
+            original_title: self.original_title.clone(),
+            year: known(&self.year),
+            rated: known(&self.rated),
+            released: known(&self.released),
+            runtime_minutes: self.runtime_minutes(),
+            genre: known(&self.genre),
+            director: known(&self.director),
+            writer: known(&self.writer),
+            actors: known(&self.actors),
+            plot: known(&self.plot),
+            language: known(&self.language),
+            country: known(&self.country),
+            awards: known(&self.awards),
+            poster: known(&self.poster),
+            metascore: self.metascore_u8(),
+            imdb_rating: self.imdb_rating_f32(),
+            imdb_votes: self.imdb_votes_u64(),
+            imdb_id: known(&self.imdb_id),
+            kind: self.kind,
+            dvd: known(&self.dvd),
+            box_office_usd: self.box_office_usd(),
+            production: known(&self.production),
+            website: known(&self.website),
+        }
+    }
+
+    /// The poster's URL, or `None` if it's unknown or not a valid
+    /// `http` or `https` URL.
+    ///
+    /// This is `url::Url`, as re-exported by `reqwest`.
+    pub fn poster_url(&self) -> Option<reqwest::Url> {
+        known_url(&self.poster)
+    }
+
+    /// The official website's URL, or `None` if it's unknown or not a
+    /// valid `http` or `https` URL.
+    ///
+    /// See `poster_url`.
+    pub fn website_url(&self) -> Option<reqwest::Url> {
+        known_url(&self.website)
+    }
+
+    /// The IMDb rating out of 10, or `None` if unknown.
+    pub fn imdb_rating_f32(&self) -> Option<f32> {
+        self.imdb_rating.parse().ok()
+    }
+
+    /// The number of IMDb votes, without OMDb's thousands separators.
+    pub fn imdb_votes_u64(&self) -> Option<u64> {
+        parse_votes(&self.imdb_votes)
+    }
+
+    /// The US box office gross in dollars, parsed from a value like
+    /// `$24,668,669`.
+    pub fn box_office_usd(&self) -> Option<u64> {
+        self.box_office
+            .trim()
+            .strip_prefix('$')?
+            .replace(',', "")
+            .parse()
+            .ok()
+    }
+
+    /// The Metascore out of 100, or `None` if unknown.
+    pub fn metascore_u8(&self) -> Option<u8> {
+        self.metascore.parse().ok()
+    }
+
+    /// The runtime in minutes, parsed from a value like `142 min`.
+    pub fn runtime_minutes(&self) -> Option<u32> {
+        parse_runtime(&self.runtime)
+    }
+
+    /// The Rotten Tomatoes score as a percentage, from `ratings`.
+    pub fn rotten_tomatoes(&self) -> Option<u8> {
+        self.rating("Rotten Tomatoes")?
+            .trim_end_matches('%')
+            .parse()
+            .ok()
+    }
+
+    /// The Metacritic score out of 100, from `ratings`.
+    pub fn metacritic(&self) -> Option<u8> {
+        self.rating("Metacritic")?.split('/').next()?.parse().ok()
+    }
+
+    /// The raw value of the rating from `source`.
+    fn rating(&self, source: &str) -> Option<&str> {
+        self.ratings
+            .iter()
+            .find(|rating| rating.source == source)
+            .map(|rating| rating.value.as_str())
+    }
+
+    /// The movie's genres, such as `Adventure` and `Family`.
+    pub fn genres(&self) -> Vec<String> {
+        split_list(&self.genre)
+    }
+
+    /// The movie's credited actors.
+    pub fn actors_list(&self) -> Vec<String> {
+        split_list(&self.actors)
+    }
+
+    /// The movie's directors.
+    pub fn directors(&self) -> Vec<String> {
+        split_list(&self.director)
+    }
+
+    /// The movie's writers, each with any parenthetical credit, like
+    /// `Noel Langley (screenplay)`.
+    pub fn writers(&self) -> Vec<String> {
+        split_list(&self.writer)
+    }
+
+    /// The languages the movie is in.
+    pub fn languages(&self) -> Vec<String> {
+        split_list(&self.language)
+    }
+
+    /// The countries the movie was made in.
+    pub fn countries(&self) -> Vec<String> {
+        split_list(&self.country)
+    }
+
+    /// The movie's genres, lowercased, for comparing with other movies.
+    ///
+    /// Movies with no known genre give an empty set.
+    pub fn genres_set(&self) -> HashSet<String> {
+        split_list(&self.genre)
+            .into_iter()
+            .map(|genre| genre.to_lowercase())
+            .collect()
+    }
+
+    /// The values of the given columns, in order, for tabular export.
+    ///
+    /// Columns are named after `Movie`'s fields, plus `runtime_minutes`
+    /// for the parsed runtime, which is empty when unknown.
+    /// Any other column name is an error.
+    pub fn to_record(&self, columns: &[&str]) -> Result<Vec<String>, Error> {
+        columns
+            .iter()
+            .map(|column| {
+                let value = match *column {
+                    "title" => self.title.clone(),
+                    "original_title" => self.original_title.clone().unwrap_or_default(),
+                    "year" => self.year.clone(),
+                    "rated" => self.rated.clone(),
+                    "released" => self.released.clone(),
+                    "runtime" => self.runtime.clone(),
+                    "runtime_minutes" => parse_runtime(&self.runtime)
+                        .map(|minutes| minutes.to_string())
+                        .unwrap_or_default(),
+                    "genre" => self.genre.clone(),
+                    "director" => self.director.clone(),
+                    "writer" => self.writer.clone(),
+                    "actors" => self.actors.clone(),
+                    "plot" => self.plot.clone(),
+                    "language" => self.language.clone(),
+                    "country" => self.country.clone(),
+                    "awards" => self.awards.clone(),
+                    "poster" => self.poster.clone(),
+                    "metascore" => self.metascore.clone(),
+                    "imdb_rating" => self.imdb_rating.clone(),
+                    "imdb_votes" => self.imdb_votes.clone(),
+                    "imdb_id" => self.imdb_id.clone(),
+                    "kind" => <&str>::from(self.kind).to_owned(),
+                    "dvd" => self.dvd.clone(),
+                    "box_office" => self.box_office.clone(),
+                    "production" => self.production.clone(),
+                    "website" => self.website.clone(),
+                    _ => return Err(Error::Other("unknown column")),
+                };
+
+                Ok(value)
+            })
+            .collect()
+    }
+
+    /// The movie as JSON shaped like OMDb's own responses.
+    ///
+    /// This uses OMDb's field names, such as `Title` and `imdbID`,
+    /// rather than `Movie`'s.
+    pub fn to_omdb_json(&self) -> serde_json::Value {
+        let kind: &str = self.kind.into();
+
+        let mut json = serde_json::json!({
+            "Title": self.title,
+            "OriginalTitle": self.original_title,
+            "Year": self.year,
+            "Rated": self.rated,
+            "Released": self.released,
+            "Runtime": self.runtime,
+            "Genre": self.genre,
+            "Director": self.director,
+            "Writer": self.writer,
+            "Actors": self.actors,
+            "Plot": self.plot,
+            "Language": self.language,
+            "Country": self.country,
+            "Awards": self.awards,
+            "Poster": self.poster,
+            "Metascore": self.metascore,
+            "imdbRating": self.imdb_rating,
+            "imdbVotes": self.imdb_votes,
+            "imdbID": self.imdb_id,
+            "Type": kind,
+            "DVD": self.dvd,
+            "BoxOffice": self.box_office,
+            "Production": self.production,
+            "Website": self.website,
+            "totalSeasons": self.total_seasons,
+            "Ratings": self
+                .ratings
+                .iter()
+                .map(|rating| serde_json::json!({"Source": rating.source, "Value": rating.value}))
+                .collect::<Vec<_>>(),
+            "Response": "True",
+        });
+
+        // Only seasons list their episodes
+        if !self.episodes.is_empty() {
+            json["Episodes"] = self
+                .episodes
+                .iter()
+                .map(|episode| {
+                    serde_json::json!({
+                        "Season": episode.season.to_string(),
+                        "Title": episode.title,
+                        "Released": episode.released,
+                        "Episode": episode.episode,
+                        "imdbRating": episode
+                            .imdb_rating
+                            .map_or_else(|| String::from("N/A"), |rating| rating.to_string()),
+                        "imdbID": episode.imdb_id,
+                    })
+                })
+                .collect();
+        }
+
+        json
+    }
+
+    /// The number of words in the plot, or zero if it's unknown.
+    pub fn plot_word_count(&self) -> usize {
+        if is_known(&self.plot) {
+            self.plot.split_whitespace().count()
+        } else {
+            0
+        }
+    }
+
+    /// Roughly how long the plot takes to read at `wpm` words per minute,
+    /// rounded to the nearest second.
+    ///
+    /// Unknown plots, and a `wpm` of zero, take no time.
+    pub fn plot_reading_time(&self, wpm: u32) -> Duration {
+        if wpm == 0 {
+            return Duration::from_secs(0);
+        }
+
+        let words = self.plot_word_count() as u64;
+        let wpm = u64::from(wpm);
+
+        Duration::from_secs((words * 60 + wpm / 2) / wpm)
+    }
+
+    /// A compact, single line description of the movie.
+    ///
+    /// For example `The Wizard of Oz (1939) • Movie • ★8.1 • 102 min`.
+    /// Unknown parts are left out.
+    pub fn summary_line(&self) -> String {
+        let mut title = self.title.clone();
+        if is_known(&self.year) {
+            title.push_str(&format!(" ({})", self.year));
+        }
+
+        let kind = match self.kind {
+            Kind::Movie => "Movie",
+            Kind::Series => "Series",
+            Kind::Episode => "Episode",
+            Kind::Game => "Game",
+        };
+
+        let mut parts = vec![title, kind.to_owned()];
+        if is_known(&self.imdb_rating) {
+            parts.push(format!("★{}", self.imdb_rating));
+        }
+        if is_known(&self.runtime) {
+            parts.push(self.runtime.clone());
+        }
+
+        parts.join(" • ")
+    }
+
+    /// A YouTube search URL for this movie's trailer.
+    ///
+    /// OMDb doesn't know about trailers, so this searches for the
+    /// title and year (when known) followed by `trailer`.
+    pub fn trailer_search_url(&self) -> String {
+        let mut terms = vec![self.title.as_str()];
+        if is_known(&self.year) {
+            terms.push(&self.year);
+        }
+        terms.push("trailer");
+
+        let query = serde_urlencoded::to_string([("search_query", terms.join(" "))])
+            .expect("a string pair always encodes");
+
+        format!("https://www.youtube.com/results?{}", query)
+    }
+
+    /// Whether the movie's rating makes it suitable for kids.
+    ///
+    /// `G`, `PG`, `TV-Y`, `TV-Y7`, `TV-G` and `TV-PG` are safe, while `R`,
+    /// `NC-17` and `TV-MA` are not. Returns `None` for anything in between
+    /// (such as `PG-13`), unrated, or unknown, leaving the decision
+    /// to the caller.
+    pub fn rated_is_kids_safe(&self) -> Option<bool> {
+        match rated_level(&self.rated)? {
+            0 | 1 => Some(true),
+            2 => None,
+            _ => Some(false),
+        }
+    }
+
+    /// Whether the movie meets every criterion set on `filter`.
+    ///
+    /// A criterion on a field the movie doesn't know, or can't be
+    /// parsed, isn't met.
+    pub fn matches(&self, filter: &MovieFilter) -> bool {
+        if let Some(min) = filter.min_rating {
+            if !self.imdb_rating_f32().is_some_and(|rating| rating >= min) {
+                return false;
+            }
+        }
+
+        if !filter.kinds.is_empty() && !filter.kinds.contains(&self.kind) {
+            return false;
+        }
+
+        if !filter.genres.is_empty() {
+            let genres = self.genres_set();
+            if !filter
+                .genres
+                .iter()
+                .any(|genre| genres.contains(&genre.to_lowercase()))
+            {
+                return false;
+            }
+        }
+
+        if let Some(years) = filter.years.as_ref() {
+            if !self
+                .year_range()
+                .is_some_and(|(start, _)| years.contains(&start))
+            {
+                return false;
+            }
+        }
+
+        if let Some(max) = filter.max_rated.as_deref() {
+            match (rated_level(&self.rated), rated_level(max)) {
+                (Some(rated), Some(max)) if rated <= max => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// Orders content ratings from most to least suitable for kids,
+/// treating comparable film and TV ratings alike.
+///
+/// This is the one table of ratings, behind both
+/// `Movie::rated_is_kids_safe` and `MovieFilter::max_rated`.
+fn rated_level(rated: &str) -> Option<u8> {
+    match rated.trim() {
+        "G" | "TV-Y" | "TV-G" => Some(0),
+        "PG" | "TV-Y7" | "TV-PG" => Some(1),
+        "PG-13" | "TV-14" => Some(2),
+        "R" | "TV-MA" => Some(3),
+        "NC-17" => Some(4),
+        _ => None,
+    }
+}
+
+/// Criteria for `Movie::matches`.
+///
+/// Unset criteria match any movie.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MovieFilter {
+    /// The lowest IMDb rating allowed.
+    pub min_rating: Option<f32>,
+    /// The kinds allowed, or any if empty.
+    pub kinds: Vec<Kind>,
+    /// Genres to look for, a movie with any of them matches.
+    pub genres: Vec<String>,
+    /// The years the movie may have started in.
+    pub years: Option<RangeInclusive<u16>>,
+    /// The highest content rating allowed, such as `PG-13`.
+    pub max_rated: Option<String>,
+}
+
+impl SearchResultsMovie {
+    /// The years the movie was released over, as `(start, end)`.
+    ///
+    /// See `Movie::year_range`.
+    pub fn year_range(&self) -> Option<(u16, Option<u16>)> {
+        parse_year_range(&self.year)
+    }
+
+    /// Starts a `FindQuery` for the full record of this result, by its
+    /// IMDb ID.
+    ///
+    /// The plot is left to OMDb, use `FindQuery::plot` to choose one.
+    pub fn fetch<S: ToString>(&self, apikey: S) -> query::FindQuery {
+        let mut query = query::imdb_id(self.imdb_id.clone());
+        query.apikey(apikey);
+        query
+    }
+}
+
+/// Parses OMDb's year field, which is a single year or a range of years.
+fn parse_year_range(year: &str) -> Option<(u16, Option<u16>)> {
+    let mut parts = year.trim().splitn(2, ['–', '-']);
+
+    let start = parts.next()?.trim().parse().ok()?;
+    let end = match parts.next().map(str::trim) {
+        None => Some(start),
+        Some("") => None,
+        Some(end) => Some(end.parse().ok()?),
+    };
+
+    Some((start, end))
+}
+
+/// Whether a field from OMDb holds a value, rather than being empty or `N/A`.
+fn is_known(value: &str) -> bool {
+    !value.is_empty() && value != "N/A"
+}
+
+/// A field from OMDb, or `None` if it's empty or `N/A`.
+fn known(value: &str) -> Option<String> {
+    if is_known(value) {
+        Some(value.to_owned())
+    } else {
+        None
+    }
+}
+
+/// A web link from OMDb, or `None` if it's unknown or isn't one.
+fn known_url(value: &str) -> Option<reqwest::Url> {
+    let url = reqwest::Url::parse(value.trim()).ok()?;
+
+    match url.scheme() {
+        "http" | "https" => Some(url),
+        _ => None,
+    }
+}
+
+/// Splits a comma separated field like `Adventure, Family, Fantasy`.
+///
+/// Commas inside parentheses, as in credits like
+/// `L. Frank Baum (novel, 1900)`, don't split the entry.
+fn split_list(value: &str) -> Vec<String> {
+    if !is_known(value) {
+        return Vec::new();
+    }
+
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&value[start..]);
+
+    items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Parses a vote count like `408,915`.
+fn parse_votes(votes: &str) -> Option<u64> {
+    votes.replace(',', "").parse().ok()
+}
+
+/// Parses a runtime like `102 min` into minutes.
+fn parse_runtime(runtime: &str) -> Option<u32> {
+    runtime.trim().trim_end_matches("min").trim().parse().ok()
+}
+
+/// A `Movie` where unknown values are `None`.
+///
+/// Created with `Movie::as_optional`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MovieView {
+    pub title: Option<String>,
+    pub original_title: Option<String>,
+    pub year: Option<String>,
+    pub rated: Option<String>,
+    pub released: Option<String>,
+    pub runtime_minutes: Option<u32>,
+    pub genre: Option<String>,
+    pub director: Option<String>,
+    pub writer: Option<String>,
+    pub actors: Option<String>,
+    pub plot: Option<String>,
+    pub language: Option<String>,
+    pub country: Option<String>,
+    pub awards: Option<String>,
+    pub poster: Option<String>,
+    pub metascore: Option<u8>,
+    pub imdb_rating: Option<f32>,
+    pub imdb_votes: Option<u64>,
+    pub imdb_id: Option<String>,
+    pub kind: Kind,
+    pub dvd: Option<String>,
+    pub box_office_usd: Option<u64>,
+    pub production: Option<String>,
+    pub website: Option<String>,
+}
+
+/// A quality tier for an IMDb rating.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RatingBucket {
+    /// Rated `EXCELLENT` or higher.
+    Excellent,
+    /// Rated `GOOD` or higher.
+    Good,
+    /// Rated `MIXED` or higher.
+    Mixed,
+    /// Rated below `MIXED`.
+    Poor,
+}
+
+impl RatingBucket {
+    /// The lowest rating that is `Excellent`.
+    pub const EXCELLENT: f32 = 8.0;
+    /// The lowest rating that is `Good`.
+    pub const GOOD: f32 = 6.5;
+    /// The lowest rating that is `Mixed`.
+    pub const MIXED: f32 = 5.0;
+
+    /// The tier of an IMDb rating out of 10.
+    pub fn from_rating(rating: f32) -> RatingBucket {
+        if rating >= RatingBucket::EXCELLENT {
+            RatingBucket::Excellent
+        } else if rating >= RatingBucket::GOOD {
+            RatingBucket::Good
+        } else if rating >= RatingBucket::MIXED {
+            RatingBucket::Mixed
+        } else {
+            RatingBucket::Poor
+        }
+    }
+}
+
+/// A popularity tier for a number of IMDb votes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VotesTier {
+    /// `BLOCKBUSTER` votes or more.
+    Blockbuster,
+    /// `POPULAR` votes or more.
+    Popular,
+    /// `NICHE` votes or more.
+    Niche,
+    /// Fewer than `NICHE` votes.
+    Obscure,
+}
+
+impl VotesTier {
+    /// The fewest votes that are `Blockbuster`.
+    pub const BLOCKBUSTER: u64 = 500_000;
+    /// The fewest votes that are `Popular`.
+    pub const POPULAR: u64 = 50_000;
+    /// The fewest votes that are `Niche`.
+    pub const NICHE: u64 = 5_000;
+
+    /// The tier of a number of IMDb votes.
+    pub fn from_votes(votes: u64) -> VotesTier {
+        if votes >= VotesTier::BLOCKBUSTER {
+            VotesTier::Blockbuster
+        } else if votes >= VotesTier::POPULAR {
+            VotesTier::Popular
+        } else if votes >= VotesTier::NICHE {
+            VotesTier::Niche
+        } else {
+            VotesTier::Obscure
+        }
+    }
+}
+
+/// A rating from one source, such as `Rotten Tomatoes` and `98%`.
+///
+/// Each source formats its value differently.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub source: String,
+    pub value: String,
+}
+
+/// An episode in a season's episode list.
+///
+/// OMDb only lists these few details, fetch the episode by its
+/// `imdb_id` for the rest.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Episode {
+    pub season: u16,
+    pub title: String,
+    pub released: String,
+    pub episode: String,
+    /// The IMDb rating out of 10, or `None` if unknown, such as for
+    /// episodes that haven't aired.
+    pub imdb_rating: Option<f32>,
+    pub imdb_id: String,
+}
+
+impl Episode {
+    /// Starts a `FindQuery` for the full record of this episode, by its
+    /// IMDb ID.
+    ///
+    /// Set the API key and any other options on the query, such as
+    /// `FindQuery::plot`, then `get` it for the episode as a `Movie`.
+    pub fn to_find_query(&self) -> query::FindQuery {
+        query::imdb_id(self.imdb_id.clone())
+    }
+}
+
+/// A season of a series, as fetched by `FindQuery::get_season`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Season {
+    /// The title of the series.
+    pub title: String,
+    pub season: u16,
+    pub total_seasons: u16,
+    pub episodes: Vec<Episode>,
 }
 
 /// Search results from OMDb.
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SearchResults {
     pub results: Vec<SearchResultsMovie>,
     pub total_results: usize,
+    /// The page these results are from, starting at 1.
+    #[serde(default = "first_page")]
+    pub page: usize,
+}
+
+/// The first page of search results.
+fn first_page() -> usize {
+    1
+}
+
+impl SearchResults {
+    /// The number of results OMDb returns per page.
+    pub const PAGE_SIZE: usize = 10;
+
+    /// The number of pages of results there are in total.
+    pub fn total_pages(&self) -> usize {
+        self.total_results.div_ceil(SearchResults::PAGE_SIZE)
+    }
+
+    /// Whether there are more pages of results after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.total_pages()
+    }
+
+    /// Remove results with the same title as an earlier result,
+    /// ignoring case.
+    ///
+    /// This only affects the fetched `results`, `total_results`
+    /// stays as OMDb reported it.
+    pub fn dedupe_by_title(&mut self) {
+        let mut seen = HashSet::new();
+        self.results
+            .retain(|movie| seen.insert(movie.title.to_lowercase()));
+    }
+
+    /// The fetched results of the given kind.
+    pub fn filter_kind(&self, kind: Kind) -> Vec<&SearchResultsMovie> {
+        self.results
+            .iter()
+            .filter(|movie| movie.kind == kind)
+            .collect()
+    }
+
+    /// The fetched results that are movies.
+    pub fn movies_only(&self) -> Vec<&SearchResultsMovie> {
+        self.filter_kind(Kind::Movie)
+    }
+
+    /// The fetched results, oldest first.
+    ///
+    /// Results are ordered by the year they started in, so a series
+    /// running `2014–` sorts as 2014. Results with an unknown year go
+    /// last, and ties keep OMDb's order.
+    pub fn sorted_by_year(&self) -> Vec<&SearchResultsMovie> {
+        let mut sorted: Vec<_> = self.results.iter().collect();
+        sorted.sort_by_key(|movie| match movie.year_range() {
+            Some((start, _)) => (false, start),
+            None => (true, 0),
+        });
+        sorted
+    }
+
+    /// Serialize the fetched results as JSON lines, one object per
+    /// result.
+    pub fn to_jsonl(&self) -> Result<String, Error> {
+        let mut jsonl = String::new();
+        for movie in self.results.iter() {
+            jsonl.push_str(&serde_json::to_string(movie)?);
+            jsonl.push('\n');
+        }
+
+        Ok(jsonl)
+    }
 }
 
 /// A movie from an OMDb search.
 ///
 /// These contain less information than a regular `Movie`.
-#[derive(Debug)]
+/// Like a `Movie`, values OMDb doesn't know are empty.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SearchResultsMovie {
     pub title: String,
     pub year: String,
@@ -56,8 +959,10 @@ pub struct SearchResultsMovie {
 
 /// Distinguishes between the different types of media available.
 ///
-/// Note that `Kind` is the same thing as OMDb's `Type`.
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Note that `Kind` is the same thing as OMDb's `Type`. More kinds may
+/// be added in minor releases, so matches on a `Kind` need a wildcard arm.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Kind {
     Movie,
     Series,
@@ -65,18 +970,28 @@ pub enum Kind {
     Game,
 }
 
-impl Kind {
-    fn from_str(from: &str) -> Option<Kind> {
-        match from {
-            "movie" => Some(Kind::Movie),
-            "series" => Some(Kind::Series),
-            "episode" => Some(Kind::Episode),
-            "game" => Some(Kind::Game),
-            _ => None,
+impl FromStr for Kind {
+    type Err = Error;
+
+    /// Parses `movie`, `series`, `episode`, or `game`, ignoring case.
+    fn from_str(from: &str) -> Result<Kind, Error> {
+        match from.to_lowercase().as_str() {
+            "movie" => Ok(Kind::Movie),
+            "series" => Ok(Kind::Series),
+            "episode" => Ok(Kind::Episode),
+            "game" => Ok(Kind::Game),
+            _ => Err(Error::Other("invalid kind")),
         }
     }
 }
 
+impl fmt::Display for Kind {
+    /// Writes the kind as OMDb names it, such as `movie`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <&'static str>::from(*self).fmt(f)
+    }
+}
+
 impl From<Kind> for &'static str {
     fn from(kind: Kind) -> &'static str {
         match kind {
@@ -89,10 +1004,38 @@ impl From<Kind> for &'static str {
 }
 
 /// Plot length.
-#[derive(Copy, Clone, Debug, PartialEq)]
+///
+/// More lengths may be added in minor releases, like `Default` was, so
+/// matches on a `Plot` need a wildcard arm.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Plot {
     Short,
     Full,
+    /// Let OMDb choose, which is currently the short plot.
+    ///
+    /// No `plot` parameter is sent, so this clears a plot set earlier.
+    Default,
+}
+
+impl FromStr for Plot {
+    type Err = Error;
+
+    /// Parses `short`, `full`, or `default`, ignoring case.
+    fn from_str(from: &str) -> Result<Plot, Error> {
+        match from.to_lowercase().as_str() {
+            "short" => Ok(Plot::Short),
+            "full" => Ok(Plot::Full),
+            "default" => Ok(Plot::Default),
+            _ => Err(Error::Other("invalid plot")),
+        }
+    }
+}
+
+impl fmt::Display for Plot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <&'static str>::from(*self).fmt(f)
+    }
 }
 
 impl From<Plot> for &'static str {
@@ -100,6 +1043,7 @@ impl From<Plot> for &'static str {
         match plot {
             Plot::Short => "short",
             Plot::Full => "full",
+            Plot::Default => "default",
         }
     }
 }
@@ -109,6 +1053,617 @@ mod tests {
     use super::*;
     use std::env;
 
+    pub(crate) fn movie() -> Movie {
+        Movie {
+            title: String::from("The Wizard of Oz"),
+            original_title: None,
+            year: String::from("1939"),
+            rated: String::from("G"),
+            released: String::from("25 Aug 1939"),
+            runtime: String::from("102 min"),
+            genre: String::from("Adventure, Family, Fantasy"),
+            director: String::from("Victor Fleming, George Cukor"),
+            writer: String::from("Noel Langley, Florence Ryerson"),
+            actors: String::from("Judy Garland, Frank Morgan, Ray Bolger"),
+            plot: String::from("Young Dorothy Gale and her dog Toto are swept away by a tornado."),
+            language: String::from("English"),
+            country: String::from("United States"),
+            awards: String::from("Won 2 Oscars"),
+            poster: String::from("https://m.media-amazon.com/images/M/oz.jpg"),
+            metascore: String::from("92"),
+            imdb_rating: String::from("8.1"),
+            imdb_votes: String::from("408,915"),
+            imdb_id: String::from("tt0032138"),
+            kind: Kind::Movie,
+            dvd: String::from("06 Dec 2005"),
+            box_office: String::from("$24,668,669"),
+            production: String::new(),
+            website: String::new(),
+            ratings: vec![
+                Rating {
+                    source: String::from("Internet Movie Database"),
+                    value: String::from("8.1/10"),
+                },
+                Rating {
+                    source: String::from("Rotten Tomatoes"),
+                    value: String::from("98%"),
+                },
+                Rating {
+                    source: String::from("Metacritic"),
+                    value: String::from("92/100"),
+                },
+            ],
+            total_seasons: String::new(),
+            episodes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn imdb_rating_stars() {
+        let mut movie = movie();
+        movie.imdb_rating = String::from("7.8");
+        assert!((movie.imdb_rating_stars(5).unwrap() - 3.9).abs() < 1e-5);
+
+        movie.imdb_rating = String::from("N/A");
+        assert_eq!(movie.imdb_rating_stars(5), None);
+    }
+
+    #[test]
+    fn imdb_rating_bucket() {
+        let mut movie = movie();
+        let mut bucket = |rating: &str| {
+            movie.imdb_rating = String::from(rating);
+            movie.imdb_rating_bucket()
+        };
+
+        assert_eq!(bucket("8.1"), Some(RatingBucket::Excellent));
+        assert_eq!(bucket("6.5"), Some(RatingBucket::Good));
+        assert_eq!(bucket("5.9"), Some(RatingBucket::Mixed));
+        assert_eq!(bucket("2.3"), Some(RatingBucket::Poor));
+        assert_eq!(bucket("N/A"), None);
+    }
+
+    #[test]
+    fn to_minimal() {
+        let movie = movie();
+        let minimal = movie.to_minimal();
+
+        assert_eq!(minimal.title, movie.title);
+        assert_eq!(minimal.year, movie.year);
+        assert_eq!(minimal.imdb_id, movie.imdb_id);
+        assert_eq!(minimal.poster, movie.poster);
+        assert_eq!(minimal.kind, movie.kind);
+    }
+
+    /// The first season of Fleabag, with `episodes` numbered as given.
+    fn season(episodes: &[u16]) -> Movie {
+        Movie {
+            title: String::from("Fleabag"),
+            year: String::from("2016–2019"),
+            runtime: String::from("27 min"),
+            imdb_id: String::from("tt5687612"),
+            kind: Kind::Series,
+            total_seasons: String::from("2"),
+            episodes: episodes
+                .iter()
+                .map(|episode| Episode {
+                    season: 1,
+                    title: format!("Episode #1.{}", episode),
+                    released: String::from("2016-07-21"),
+                    episode: episode.to_string(),
+                    imdb_rating: Some(8.4),
+                    imdb_id: format!("tt56887{:02}", episode),
+                })
+                .collect(),
+            ..movie()
+        }
+    }
+
+    #[test]
+    fn clone_without_episodes() {
+        let season = season(&[1, 2, 3]);
+        let trimmed = season.clone_without_episodes();
+
+        assert!(trimmed.episodes.is_empty());
+        assert_eq!(trimmed.title, season.title);
+        assert_eq!(trimmed.imdb_id, season.imdb_id);
+        assert_eq!(season.episodes.len(), 3);
+    }
+
+    #[test]
+    fn contiguous_episodes() {
+        let complete = season(&[2, 1, 3]);
+        assert_eq!(complete.episode_count(), 3);
+        assert!(complete.has_contiguous_episodes());
+
+        let gapped = season(&[1, 2, 4]);
+        assert_eq!(gapped.episode_count(), 3);
+        assert!(!gapped.has_contiguous_episodes());
+
+        assert!(!season(&[1, 1]).has_contiguous_episodes());
+        assert!(!movie().has_contiguous_episodes());
+    }
+
+    #[test]
+    fn estimated_season_runtime() {
+        let mut season = season(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            season.estimated_season_runtime(),
+            Some(Duration::from_secs(6 * 27 * 60))
+        );
+
+        season.runtime = String::from("N/A");
+        assert_eq!(season.estimated_season_runtime(), None);
+        assert_eq!(movie().estimated_season_runtime(), None);
+    }
+
+    #[test]
+    fn urls() {
+        let mut movie = movie();
+        movie.website = String::from("https://www.warnerbros.com/movies/wizard-oz");
+        assert_eq!(
+            movie.website_url().unwrap().host_str(),
+            Some("www.warnerbros.com")
+        );
+        assert_eq!(
+            movie.poster_url().unwrap().as_str(),
+            "https://m.media-amazon.com/images/M/oz.jpg"
+        );
+
+        movie.website = String::from("N/A");
+        assert_eq!(movie.website_url(), None);
+        movie.website = String::from("mailto:oz@example.com");
+        assert_eq!(movie.website_url(), None);
+        movie.poster = String::from("N/A");
+        assert_eq!(movie.poster_url(), None);
+    }
+
+    #[test]
+    fn as_optional() {
+        let mut movie = movie();
+        movie.plot = String::from("N/A");
+        movie.awards = String::new();
+        movie.metascore = String::from("N/A");
+
+        let view = movie.as_optional();
+        assert_eq!(view.title.as_deref(), Some("The Wizard of Oz"));
+        assert_eq!(view.plot, None);
+        assert_eq!(view.awards, None);
+        assert_eq!(view.metascore, None);
+        assert_eq!(view.runtime_minutes, Some(102));
+        assert_eq!(view.imdb_rating, Some(8.1));
+        assert_eq!(view.imdb_votes, Some(408_915));
+    }
+
+    #[test]
+    fn typed_accessors() {
+        let mut movie = movie();
+        movie.metascore = String::from("92");
+        assert_eq!(movie.imdb_rating_f32(), Some(8.1));
+        assert_eq!(movie.imdb_votes_u64(), Some(408_915));
+        assert_eq!(movie.metascore_u8(), Some(92));
+        assert_eq!(movie.runtime_minutes(), Some(102));
+        assert_eq!(movie.box_office_usd(), Some(24_668_669));
+
+        movie.imdb_rating = String::from("N/A");
+        movie.imdb_votes = String::from("N/A");
+        movie.metascore = String::from("N/A");
+        movie.runtime = String::from("N/A");
+        movie.box_office = String::new();
+        assert_eq!(movie.imdb_rating_f32(), None);
+        assert_eq!(movie.imdb_votes_u64(), None);
+        assert_eq!(movie.metascore_u8(), None);
+        assert_eq!(movie.runtime_minutes(), None);
+        assert_eq!(movie.box_office_usd(), None);
+    }
+
+    #[test]
+    fn ratings() {
+        let mut movie = movie();
+        assert_eq!(movie.rotten_tomatoes(), Some(98));
+        assert_eq!(movie.metacritic(), Some(92));
+
+        movie.ratings.truncate(1);
+        assert_eq!(movie.rotten_tomatoes(), None);
+        assert_eq!(movie.metacritic(), None);
+    }
+
+    #[test]
+    fn matches() {
+        let filter = MovieFilter {
+            min_rating: Some(7.5),
+            kinds: vec![Kind::Movie],
+            genres: vec![String::from("Fantasy"), String::from("Western")],
+            years: Some(1930..=1949),
+            max_rated: Some(String::from("PG")),
+        };
+
+        let oz = movie();
+        assert!(oz.matches(&filter));
+        assert!(oz.matches(&MovieFilter::default()));
+
+        let mut low_rated = movie();
+        low_rated.imdb_rating = String::from("6.9");
+        assert!(!low_rated.matches(&filter));
+
+        let mut series = movie();
+        series.kind = Kind::Series;
+        assert!(!series.matches(&filter));
+
+        let mut later = movie();
+        later.year = String::from("1985");
+        later.rated = String::from("PG-13");
+        assert!(!later.matches(&MovieFilter {
+            years: filter.years.clone(),
+            ..MovieFilter::default()
+        }));
+        assert!(!later.matches(&MovieFilter {
+            max_rated: filter.max_rated.clone(),
+            ..MovieFilter::default()
+        }));
+
+        // Unknown values fail their criterion
+        let mut unknown = movie();
+        unknown.imdb_rating = String::new();
+        unknown.rated = String::from("Not Rated");
+        assert!(!unknown.matches(&MovieFilter {
+            min_rating: Some(1.0),
+            ..MovieFilter::default()
+        }));
+        assert!(!unknown.matches(&MovieFilter {
+            max_rated: Some(String::from("NC-17")),
+            ..MovieFilter::default()
+        }));
+    }
+
+    #[test]
+    fn list_accessors() {
+        let mut movie = movie();
+        movie.writer = String::from(
+            "Noel Langley (screenplay), L. Frank Baum (novel, 1900), Florence Ryerson",
+        );
+
+        assert_eq!(movie.genres(), vec!["Adventure", "Family", "Fantasy"]);
+        assert_eq!(
+            movie.writers(),
+            vec![
+                "Noel Langley (screenplay)",
+                "L. Frank Baum (novel, 1900)",
+                "Florence Ryerson"
+            ]
+        );
+        assert_eq!(movie.languages(), vec!["English"]);
+
+        movie.director = String::new();
+        assert!(movie.directors().is_empty());
+    }
+
+    #[test]
+    fn genres_set() {
+        let mut movie = movie();
+        movie.genre = String::from("Action, Drama");
+
+        let genres = movie.genres_set();
+        assert_eq!(genres.len(), 2);
+        assert!(genres.contains("action") && genres.contains("drama"));
+
+        movie.genre = String::from("N/A");
+        assert!(movie.genres_set().is_empty());
+    }
+
+    #[test]
+    fn to_record() {
+        let movie = movie();
+
+        assert_eq!(
+            movie
+                .to_record(&["title", "year", "imdb_rating", "runtime_minutes"])
+                .unwrap(),
+            vec!["The Wizard of Oz", "1939", "8.1", "102"]
+        );
+        assert!(movie.to_record(&["title", "budget"]).is_err());
+    }
+
+    #[test]
+    fn votes_tier() {
+        let mut movie = movie();
+        let mut tier = |votes: &str| {
+            movie.imdb_votes = String::from(votes);
+            movie.votes_tier()
+        };
+
+        assert_eq!(tier("2,640,318"), Some(VotesTier::Blockbuster));
+        assert_eq!(tier("408,915"), Some(VotesTier::Popular));
+        assert_eq!(tier("5,000"), Some(VotesTier::Niche));
+        assert_eq!(tier("87"), Some(VotesTier::Obscure));
+        assert_eq!(tier("N/A"), None);
+    }
+
+    #[test]
+    fn dedupe_by_title() {
+        let hit = |title: &str, imdb_id: &str| SearchResultsMovie {
+            title: String::from(title),
+            year: String::from("1939"),
+            imdb_id: String::from(imdb_id),
+            poster: String::new(),
+            kind: Kind::Movie,
+        };
+
+        let mut results = SearchResults {
+            results: vec![
+                hit("The Wizard of Oz", "tt0032138"),
+                hit("The Wizard of OZ", "tt0016544"),
+                hit("The Wonderful Wizard of Oz", "tt0000702"),
+            ],
+            total_results: 3,
+            page: 1,
+        };
+        results.dedupe_by_title();
+
+        let ids: Vec<_> = results.results.iter().map(|m| m.imdb_id.as_str()).collect();
+        assert_eq!(ids, vec!["tt0032138", "tt0000702"]);
+        assert_eq!(results.total_results, 3);
+    }
+
+    #[test]
+    fn filter_and_sort() {
+        let hit = |year: &str, imdb_id: &str, kind: Kind| SearchResultsMovie {
+            title: String::from("Batman"),
+            year: String::from(year),
+            imdb_id: String::from(imdb_id),
+            poster: String::new(),
+            kind,
+        };
+
+        let results = SearchResults {
+            results: vec![
+                hit("1989", "tt0096895", Kind::Movie),
+                hit("N/A", "tt0000001", Kind::Game),
+                hit("2014–", "tt3749900", Kind::Series),
+                hit("1966–1968", "tt0059968", Kind::Series),
+                hit("1966", "tt0060153", Kind::Movie),
+            ],
+            total_results: 5,
+            page: 1,
+        };
+
+        let ids = |movies: Vec<&SearchResultsMovie>| -> Vec<String> {
+            movies.iter().map(|m| m.imdb_id.clone()).collect()
+        };
+
+        assert_eq!(
+            ids(results.filter_kind(Kind::Series)),
+            vec!["tt3749900", "tt0059968"]
+        );
+        assert_eq!(ids(results.movies_only()), vec!["tt0096895", "tt0060153"]);
+        assert!(results.filter_kind(Kind::Episode).is_empty());
+        assert_eq!(
+            ids(results.sorted_by_year()),
+            vec![
+                "tt0059968",
+                "tt0060153",
+                "tt0096895",
+                "tt3749900",
+                "tt0000001"
+            ]
+        );
+    }
+
+    #[test]
+    fn total_pages() {
+        let mut results = SearchResults {
+            results: Vec::new(),
+            total_results: 25,
+            page: 1,
+        };
+        assert_eq!(results.total_pages(), 3);
+        assert!(results.has_next_page());
+
+        results.page = 3;
+        assert!(!results.has_next_page());
+
+        results.total_results = 0;
+        results.page = 1;
+        assert_eq!(results.total_pages(), 0);
+        assert!(!results.has_next_page());
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let movie = movie();
+        let mut other = movie.clone();
+        assert_eq!(movie, other);
+
+        other.ratings.clear();
+        assert_ne!(movie, other);
+
+        let results = SearchResults {
+            results: vec![movie.to_minimal()],
+            total_results: 1,
+            page: 1,
+        };
+        assert_eq!(results.clone(), results);
+        assert_eq!(results.results[0], other.to_minimal());
+    }
+
+    #[test]
+    fn search_results_round_trip() {
+        let results = SearchResults {
+            results: vec![movie().to_minimal()],
+            total_results: 1,
+            page: 1,
+        };
+
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["total_results"], 1);
+        assert_eq!(json["results"][0]["imdb_id"], "tt0032138");
+
+        let again: SearchResults = serde_json::from_value(json).unwrap();
+        assert_eq!(again, results);
+    }
+
+    #[test]
+    fn to_jsonl() {
+        let results = SearchResults {
+            results: vec![movie().to_minimal(), movie().to_minimal()],
+            total_results: 2,
+            page: 1,
+        };
+
+        let jsonl = results.to_jsonl().unwrap();
+        let lines: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), results.results.len());
+        assert_eq!(lines[0]["imdb_id"], "tt0032138");
+        assert_eq!(lines[1]["kind"], "Movie");
+    }
+
+    #[test]
+    fn plot_reading_time() {
+        let mut movie = movie();
+        assert_eq!(movie.plot_word_count(), 13);
+        assert_eq!(movie.plot_reading_time(200), Duration::from_secs(4));
+
+        movie.plot = String::from("N/A");
+        assert_eq!(movie.plot_word_count(), 0);
+        assert_eq!(movie.plot_reading_time(200), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn kind_from_str() {
+        assert_eq!("series".parse::<Kind>().unwrap(), Kind::Series);
+        assert_eq!("Series".parse::<Kind>().unwrap(), Kind::Series);
+        assert_eq!("SERIES".parse::<Kind>().unwrap(), Kind::Series);
+        assert_eq!("Movie".parse::<Kind>().unwrap(), Kind::Movie);
+        assert_eq!(
+            "book".parse::<Kind>().unwrap_err().to_string(),
+            "invalid kind"
+        );
+    }
+
+    #[test]
+    fn plot_round_trip() {
+        for plot in &[Plot::Short, Plot::Full, Plot::Default] {
+            assert_eq!(plot.to_string().parse::<Plot>().unwrap(), *plot);
+        }
+        assert_eq!("FULL".parse::<Plot>().unwrap(), Plot::Full);
+        assert!("long".parse::<Plot>().is_err());
+    }
+
+    #[test]
+    fn fetch_search_result() {
+        let query = movie().to_minimal().fetch("1a2b3c4d");
+        assert_eq!(
+            query.params(),
+            vec![
+                ("i", String::from("tt0032138")),
+                ("apikey", String::from("1a2b3c4d")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn episode_to_find_query() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"Episode #1.1","imdbID":"tt5688701","Season":"1","Episode":"1","Type":"episode","Plot":"Fleabag is a hilarious and poignant window into the mind of a dry-witted woman."}"#,
+        ]);
+
+        let episode = &season(&[1]).episodes[0];
+        let movie = episode
+            .to_find_query()
+            .apikey("1a2b3c4d")
+            .plot(Plot::Full)
+            .base_url(&url)
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(movie.imdb_id, episode.imdb_id);
+        assert_eq!(movie.kind, Kind::Episode);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("&i=tt5688701&apikey=1a2b3c4d&plot=full "));
+    }
+
+    #[test]
+    fn kind_display() {
+        for kind in &[Kind::Movie, Kind::Series, Kind::Episode, Kind::Game] {
+            assert_eq!(kind.to_string().parse::<Kind>().unwrap(), *kind);
+        }
+        assert_eq!(Kind::Episode.to_string(), "episode");
+    }
+
+    #[test]
+    fn summary_line() {
+        let mut movie = movie();
+        assert_eq!(
+            movie.summary_line(),
+            "The Wizard of Oz (1939) • Movie • ★8.1 • 102 min"
+        );
+
+        movie.year = String::new();
+        movie.imdb_rating = String::from("N/A");
+        movie.runtime = String::from("N/A");
+        assert_eq!(movie.summary_line(), "The Wizard of Oz • Movie");
+    }
+
+    #[test]
+    fn trailer_search_url() {
+        let mut movie = movie();
+        assert_eq!(
+            movie.trailer_search_url(),
+            "https://www.youtube.com/results?search_query=The+Wizard+of+Oz+1939+trailer"
+        );
+
+        movie.title = String::from("Amélie & Co?");
+        movie.year = String::from("N/A");
+        assert_eq!(
+            movie.trailer_search_url(),
+            "https://www.youtube.com/results?search_query=Am%C3%A9lie+%26+Co%3F+trailer"
+        );
+    }
+
+    #[test]
+    fn rated_is_kids_safe() {
+        let mut movie = movie();
+        let mut kids_safe = |rated: &str| {
+            movie.rated = String::from(rated);
+            movie.rated_is_kids_safe()
+        };
+
+        assert_eq!(kids_safe("G"), Some(true));
+        assert_eq!(kids_safe("TV-PG"), Some(true));
+        assert_eq!(kids_safe("TV-Y7"), Some(true));
+        assert_eq!(kids_safe("R"), Some(false));
+        assert_eq!(kids_safe("TV-MA"), Some(false));
+        assert_eq!(kids_safe("PG-13"), None);
+        assert_eq!(kids_safe("TV-14"), None);
+        assert_eq!(kids_safe("N/A"), None);
+    }
+
+    #[test]
+    fn year_range() {
+        let mut hit = SearchResultsMovie {
+            title: String::from("Game of Thrones"),
+            year: String::from("2011–2019"),
+            imdb_id: String::from("tt0944947"),
+            poster: String::new(),
+            kind: Kind::Series,
+        };
+        assert_eq!(hit.year_range(), Some((2011, Some(2019))));
+
+        hit.year = String::from("2014–");
+        assert_eq!(hit.year_range(), Some((2014, None)));
+
+        hit.year = String::from("1939");
+        assert_eq!(hit.year_range(), Some((1939, Some(1939))));
+
+        hit.year = String::from("N/A");
+        assert_eq!(hit.year_range(), None);
+    }
+
     #[tokio::test]
     async fn imdb_id() {
         let apikey = env::var("OMDB_APIKEY").expect("OMDB_APIKEY must be set");
@@ -0,0 +1,75 @@
+//! A tiny HTTP server standing in for OMDb in tests.
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Serve each of `bodies` as JSON to one request, in order.
+///
+/// Returns the server's base URL and a handle that resolves to the
+/// head of every request received once all bodies have been served.
+pub fn serve<S: Into<String>>(bodies: Vec<S>) -> (String, JoinHandle<Vec<String>>) {
+    serve_as("application/json", bodies)
+}
+
+/// Serve each of `bodies` like `serve`, waiting `delay` before each
+/// response.
+pub fn serve_delayed<S: Into<String>>(
+    delay: Duration,
+    bodies: Vec<S>,
+) -> (String, JoinHandle<Vec<String>>) {
+    spawn("application/json", delay, bodies)
+}
+
+/// Serve each of `bodies` with the given content type, like `serve`.
+pub fn serve_as<S: Into<String>>(
+    content_type: &'static str,
+    bodies: Vec<S>,
+) -> (String, JoinHandle<Vec<String>>) {
+    spawn(content_type, Duration::from_secs(0), bodies)
+}
+
+/// Serves `bodies` from a new thread, for `serve` and its variants.
+fn spawn<S: Into<String>>(
+    content_type: &'static str,
+    delay: Duration,
+    bodies: Vec<S>,
+) -> (String, JoinHandle<Vec<String>>) {
+    let bodies: Vec<String> = bodies.into_iter().map(Into::into).collect();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for body in bodies {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut head = Vec::new();
+            let mut buf = [0; 1024];
+            while !head.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                if read == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..read]);
+            }
+            requests.push(String::from_utf8_lossy(&head).into_owned());
+
+            thread::sleep(delay);
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                content_type,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+
+        requests
+    });
+
+    (url, handle)
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+#[cfg(feature = "image")]
+use std::io::Cursor;
+
+use futures_util::stream::{self, StreamExt};
+#[cfg(feature = "image")]
+use image::io::Reader;
+use reqwest::header::CONTENT_TYPE;
+
+use crate::{Error, Movie, SearchResults};
+
+/// A downloaded poster image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PosterImage {
+    /// The encoded image.
+    pub bytes: Vec<u8>,
+    /// The image's content type, such as `image/jpeg`, if the server
+    /// sent one.
+    pub content_type: Option<String>,
+}
+
+impl Movie {
+    /// Download this movie's poster.
+    ///
+    /// Movies without a poster fail with `Error::Other`.
+    pub async fn download_poster(&self, client: &reqwest::Client) -> Result<PosterImage, Error> {
+        let response = request(client, &self.poster).await?;
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+
+        Ok(PosterImage {
+            bytes: response.bytes().await?.to_vec(),
+            content_type,
+        })
+    }
+}
+
+impl SearchResults {
+    /// Download the posters of these results, `concurrency` at a time.
+    ///
+    /// Posters are keyed by IMDb ID, and results without a poster are
+    /// skipped. A failed download doesn't stop the others.
+    pub async fn prefetch_posters(
+        &self,
+        client: &reqwest::Client,
+        concurrency: usize,
+    ) -> HashMap<String, Result<Vec<u8>, Error>> {
+        let downloads =
+            self.results
+                .iter()
+                .filter(|movie| crate::is_known(&movie.poster))
+                .map(|movie| async move {
+                    (movie.imdb_id.clone(), download(client, &movie.poster).await)
+                });
+
+        stream::iter(downloads)
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+}
+
+#[cfg(feature = "image")]
+impl Movie {
+    /// Download this movie's poster and find its dominant color.
+    ///
+    /// The dominant color is the average of the most common group of
+    /// similar colors in the poster, as `[r, g, b]`.
+    pub async fn poster_dominant_color(&self, client: &reqwest::Client) -> Result<[u8; 3], Error> {
+        let bytes = download(client, &self.poster).await?;
+
+        dominant_color(&bytes)
+    }
+
+    /// Find the width and height of this movie's poster.
+    ///
+    /// Only as much of the poster as is needed to read its
+    /// dimensions is downloaded.
+    pub async fn poster_dimensions(&self, client: &reqwest::Client) -> Result<(u32, u32), Error> {
+        let mut response = request(client, &self.poster).await?;
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+
+            if let Some(dimensions) = dimensions(&bytes) {
+                return Ok(dimensions);
+            }
+        }
+
+        Err(Error::Other("failed to decode poster"))
+    }
+}
+
+/// Request the image at `poster`, which may be OMDb's `N/A`.
+async fn request(client: &reqwest::Client, poster: &str) -> Result<reqwest::Response, Error> {
+    if poster.is_empty() || poster == "N/A" {
+        return Err(Error::Other("movie has no poster"));
+    }
+
+    let response = client.get(poster).send().await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::Status(status));
+    }
+
+    Ok(response)
+}
+
+/// Download the image at `poster`, which may be OMDb's `N/A`.
+async fn download(client: &reqwest::Client, poster: &str) -> Result<Vec<u8>, Error> {
+    Ok(request(client, poster).await?.bytes().await?.to_vec())
+}
+
+/// Read the dimensions of a possibly incomplete encoded image.
+#[cfg(feature = "image")]
+fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// Find the dominant color of an encoded image.
+#[cfg(feature = "image")]
+fn dominant_color(bytes: &[u8]) -> Result<[u8; 3], Error> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|_| Error::Other("failed to decode poster"))?
+        .to_rgb8();
+
+    // Group colors by the top 4 bits of each channel, summing the
+    // pixels in each group so the winner can be averaged
+    let mut groups: HashMap<[u8; 3], (u64, [u64; 3])> = HashMap::new();
+    for pixel in image.pixels() {
+        let [r, g, b] = pixel.0;
+        let (count, sums) = groups.entry([r >> 4, g >> 4, b >> 4]).or_default();
+
+        *count += 1;
+        sums[0] += u64::from(r);
+        sums[1] += u64::from(g);
+        sums[2] += u64::from(b);
+    }
+
+    let (count, sums) = groups
+        .values()
+        .max_by_key(|(count, _)| *count)
+        .ok_or(Error::Other("poster has no pixels"))?;
+
+    Ok([
+        (sums[0] / count) as u8,
+        (sums[1] / count) as u8,
+        (sums[2] / count) as u8,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "image")]
+    use image::{ImageOutputFormat, Rgb, RgbImage};
+
+    #[cfg(feature = "image")]
+    fn png(width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        RgbImage::from_pixel(width, height, Rgb(color))
+            .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+            .unwrap();
+
+        bytes
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn solid_color() {
+        assert_eq!(
+            dominant_color(&png(8, 12, [200, 30, 60])).unwrap(),
+            [200, 30, 60]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn header_dimensions() {
+        let bytes = png(300, 450, [0, 0, 0]);
+
+        assert_eq!(dimensions(&bytes[..64]), Some((300, 450)));
+        assert_eq!(dimensions(b"<html></html>"), None);
+    }
+
+    #[tokio::test]
+    async fn no_poster() {
+        let client = reqwest::Client::new();
+
+        assert!(download(&client, "N/A").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn prefetch_posters() {
+        use crate::{Kind, SearchResultsMovie};
+
+        let (url, server) = crate::mock::serve_as("image/png", vec!["not really a png"]);
+        let hit = |imdb_id: &str, poster: String| SearchResultsMovie {
+            title: String::from("The Wizard of Oz"),
+            year: String::from("1939"),
+            imdb_id: String::from(imdb_id),
+            poster,
+            kind: Kind::Movie,
+        };
+
+        let results = SearchResults {
+            results: vec![
+                hit("tt0032138", format!("{}/oz.png", url)),
+                hit("tt0016544", String::new()),
+            ],
+            total_results: 2,
+            page: 1,
+        };
+
+        let client = reqwest::Client::new();
+        let posters = results.prefetch_posters(&client, 4).await;
+        server.join().unwrap();
+
+        assert_eq!(posters.len(), 1);
+        assert_eq!(posters["tt0032138"].as_ref().unwrap(), b"not really a png");
+    }
+
+    #[tokio::test]
+    async fn download_poster() {
+        let (url, server) = crate::mock::serve_as("image/jpeg", vec!["not really a jpeg"]);
+        let client = reqwest::Client::new();
+
+        let mut movie = crate::tests::movie();
+        movie.poster = format!("{}/oz.jpg", url);
+
+        let poster = movie.download_poster(&client).await.unwrap();
+        server.join().unwrap();
+        assert_eq!(poster.bytes, b"not really a jpeg");
+        assert_eq!(poster.content_type.as_deref(), Some("image/jpeg"));
+
+        movie.poster = String::new();
+        assert!(movie.download_poster(&client).await.is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{Movie, SearchResults};
+
+/// A cache of movies that lookups check before sending a request, such
+/// as one backed by Redis, sled or files.
+///
+/// Set one with `Client::with_cache_backend`. Keys are made from a
+/// request's parameters other than the API key, so they're the same
+/// for every key, and safe to share between processes.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+///
+/// #[derive(Debug, Default)]
+/// struct Memory(Mutex<HashMap<String, omdb::Movie>>);
+///
+/// impl omdb::CacheBackend for Memory {
+///     fn get(&self, key: &str) -> Option<omdb::Movie> {
+///         self.0.lock().unwrap().get(key).cloned()
+///     }
+///
+///     fn put(&self, key: &str, movie: omdb::Movie) {
+///         self.0.lock().unwrap().insert(key.to_owned(), movie);
+///     }
+/// }
+///
+/// let mut client = omdb::Client::new("1a2b3c4d");
+/// client.with_cache_backend(std::sync::Arc::new(Memory::default()));
+/// ```
+pub trait CacheBackend: fmt::Debug + Send + Sync {
+    /// The movie stored for `key`, if there is one.
+    fn get(&self, key: &str) -> Option<Movie>;
+
+    /// Stores `movie` for `key`.
+    fn put(&self, key: &str, movie: Movie);
+}
+
+/// A successful response, as cached.
+#[derive(Clone, Debug)]
+pub enum Cached {
+    Movie(Box<Movie>),
+    Search(SearchResults),
+}
+
+/// The parameters of a request, other than the API key.
+pub type Key = Vec<(String, String)>;
+
+/// `key` as a string for a `CacheBackend`, like a query string.
+pub fn key_string(key: &Key) -> String {
+    serde_urlencoded::to_string(key).expect("string pairs always encode")
+}
+
+/// An in-memory cache of responses shared by every query from a
+/// `Client`.
+///
+/// Entries expire `ttl` after they're stored, and once `capacity`
+/// entries are stored the least recently used one is evicted.
+#[derive(Debug)]
+pub struct Cache {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    entries: HashMap<Key, Entry>,
+    // Incremented on every use, to find the least recently used entry
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Cached,
+    stored: Instant,
+    used: u64,
+}
+
+impl Cache {
+    /// A cache of up to `capacity` responses, each kept for `ttl`.
+    pub fn new(capacity: usize, ttl: Duration) -> Cache {
+        Cache {
+            capacity,
+            ttl,
+            state: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// The response stored for `key`, if it hasn't expired.
+    pub fn get(&self, key: &Key) -> Option<Cached> {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.clock += 1;
+        let clock = state.clock;
+
+        let expired = match state.entries.get_mut(key) {
+            Some(entry) if entry.stored.elapsed() < self.ttl => {
+                entry.used = clock;
+                return Some(entry.value.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            state.entries.remove(key);
+        }
+
+        None
+    }
+
+    /// Stores `value` for `key`, evicting the least recently used
+    /// response if the cache is full.
+    pub fn insert(&self, key: Key, value: Cached) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.clock += 1;
+        let clock = state.clock;
+
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+
+        state.entries.insert(
+            key,
+            Entry {
+                value,
+                stored: Instant::now(),
+                used: clock,
+            },
+        );
+    }
+
+    /// Removes every stored response.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(title: &str) -> Key {
+        vec![(String::from("t"), String::from(title))]
+    }
+
+    fn results(total_results: usize) -> Cached {
+        Cached::Search(SearchResults {
+            results: Vec::new(),
+            total_results,
+            page: 1,
+        })
+    }
+
+    fn total(cached: Option<Cached>) -> Option<usize> {
+        match cached? {
+            Cached::Search(results) => Some(results.total_results),
+            Cached::Movie(_) => None,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = Cache::new(2, Duration::from_secs(60));
+        cache.insert(key("a"), results(1));
+        cache.insert(key("b"), results(2));
+
+        // "a" is now used more recently than "b"
+        assert_eq!(total(cache.get(&key("a"))), Some(1));
+        cache.insert(key("c"), results(3));
+
+        assert_eq!(total(cache.get(&key("a"))), Some(1));
+        assert_eq!(total(cache.get(&key("b"))), None);
+        assert_eq!(total(cache.get(&key("c"))), Some(3));
+
+        cache.clear();
+        assert_eq!(total(cache.get(&key("a"))), None);
+    }
+
+    #[test]
+    fn expires() {
+        let cache = Cache::new(2, Duration::from_secs(0));
+        cache.insert(key("a"), results(1));
+        assert_eq!(total(cache.get(&key("a"))), None);
+    }
+}
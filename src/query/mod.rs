@@ -1,39 +1,638 @@
+use futures_util::stream::{self, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, HOST};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::future::Future;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
 mod model;
 use self::model::{FindResponse, SearchResponse};
 
-use crate::{Error, Kind, Movie, Plot, SearchResults};
+mod limit;
+pub(crate) use self::limit::RateLimiter;
+
+mod cache;
+pub(crate) use self::cache::Cache;
+pub use self::cache::CacheBackend;
+use self::cache::Cached;
+
+mod quota;
+pub(crate) use self::quota::Quota;
+
+use crate::{
+    ApiError, Episode, Error, Kind, Movie, Plot, SearchResults, SearchResultsMovie, Season,
+    Transport,
+};
+
+/// The number of results OMDb returns per search page.
+const PAGE_SIZE: usize = SearchResults::PAGE_SIZE;
+
+/// Sends a request to OMDb and decodes its JSON response, through the
+/// query's `Transport` if it has one.
+async fn send<T: DeserializeOwned>(
+    http: &Http,
+    params: Vec<(&str, String)>,
+    headers: &[(String, String)],
+) -> Result<T, Error> {
+    let transport = match http.transport.as_ref() {
+        Some(transport) => transport,
+        None => {
+            let response = get_request(http, params, headers).await?;
+            return decode(response, http.max_response_bytes).await;
+        }
+    };
+
+    let url = build_request(http, params, headers)
+        .build()?
+        .url()
+        .to_string();
+
+    let body = with_retries(http.retries, http.retry_backoff, || async {
+        http.before_send().await?;
+        let body = transport.get(url.clone(), headers.to_vec()).await;
+        http.sent(&body);
+
+        body
+    })
+    .await?;
+
+    if http.max_response_bytes.is_some_and(|max| body.len() > max) {
+        return Err(Error::Other("response too large"));
+    }
+
+    let is_html = body.trim_start().starts_with('<');
+    decode_body(&body, is_html)
+}
 
 /// A function to create and send a request to OMDb.
-async fn get_request<I, K, V>(params: I) -> Result<reqwest::Response, Error>
+async fn get_request<I, K, V>(
+    http: &Http,
+    params: I,
+    headers: &[(String, String)],
+) -> Result<reqwest::Response, Error>
 where
     I: IntoIterator,
     I::Item: Borrow<(K, V)> + Serialize,
     K: AsRef<str> + Serialize,
     V: AsRef<str> + Serialize,
 {
+    let mut request = build_request(http, params, headers).build()?;
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(url = %redact(request.url()), "sending request to OMDb");
+
+    http.resolve(&mut request);
+    let client = http.client();
+
+    with_retries(http.retries, http.retry_backoff, || async {
+        let request = request
+            .try_clone()
+            .expect("requests without a body can be cloned");
+
+        http.before_send().await?;
+        let response = async {
+            let response = client.execute(request).await?;
+
+            let status = response.status();
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(status = status.as_u16(), "received response from OMDb");
+
+            if !status.is_success() {
+                return Err(Error::Status(status));
+            }
+
+            Ok(response)
+        }
+        .await;
+        http.sent(&response);
+
+        response
+    })
+    .await
+}
+
+/// `url` with its API key replaced by `REDACTED`, for logging.
+#[cfg(feature = "tracing")]
+fn redact(url: &reqwest::Url) -> String {
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| match key.as_ref() {
+            "apikey" => (key.into_owned(), String::from("REDACTED")),
+            _ => (key.into_owned(), value.into_owned()),
+        })
+        .collect();
+
+    let mut url = url.clone();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url.to_string()
+}
+
+/// An `Api` error for an error message from OMDb, logged as a warning.
+fn api_error(message: &str) -> Error {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(error = message, "OMDb returned an error");
+
+    Error::api(message)
+}
+
+/// The default base delay between retries.
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The longest delay between retries, however many there have been.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Calls `send` until it succeeds, fails for good, or has been retried
+/// `retries` times, backing off exponentially from `base` in between.
+async fn with_retries<T, F, Fut>(retries: u32, base: Duration, mut send: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        match send().await {
+            Err(ref err) if attempt < retries && is_transient(err) => {
+                tokio::time::sleep(backoff(base, attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether a request that failed with `err` might succeed if retried.
+///
+/// Server errors, timeouts and connection failures are retried, while
+/// OMDb's own errors and other statuses would just fail again.
+fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::Status(status) => status.is_server_error(),
+        Error::Http(err) => err.is_timeout() || err.is_connect(),
+        _ => false,
+    }
+}
+
+/// How long to wait before retry number `attempt`, counting from zero.
+///
+/// This doubles from `base` with each attempt, up to `MAX_RETRY_DELAY`,
+/// and a random half of it is skipped so clients retrying together
+/// spread out.
+fn backoff(base: Duration, attempt: u32) -> Duration {
+    let delay = 2u32
+        .checked_pow(attempt)
+        .and_then(|factor| base.checked_mul(factor))
+        .map_or(MAX_RETRY_DELAY, |delay| delay.min(MAX_RETRY_DELAY));
+
+    // Each `RandomState` is randomly seeded
+    let jitter = RandomState::new().build_hasher().finish() % 1000;
+    delay / 2 + delay / 2 * jitter as u32 / 1000
+}
+
+/// The base URL requests are sent to.
+///
+/// This is the query's `base_url` if it has one, then `env_base_url`,
+/// the value of `OMDB_BASE_URL`, if it's set, otherwise OMDb itself.
+fn endpoint(base_url: Option<&str>, env_base_url: Option<String>) -> String {
     const API_ENDPOINT: &str = "https://omdbapi.com";
-    const API_VERSION: &str = "1";
 
+    match base_url {
+        Some(base_url) => base_url.to_owned(),
+        None => env_base_url.unwrap_or_else(|| API_ENDPOINT.to_owned()),
+    }
+}
+
+/// How a query's requests are sent.
+///
+/// Queries from an `omdb::Client`, or given one with `with_client`,
+/// share its connection pool. Others create a client for each request.
+/// Only the API version and plot defaults shape a request's parameters,
+/// so everything else here is ignored when comparing or hashing queries.
+#[derive(Clone, Debug)]
+pub(crate) struct Http {
+    pub(crate) client: Option<reqwest::Client>,
+    pub(crate) limiter: Option<Arc<RateLimiter>>,
+    pub(crate) cache: Option<Arc<Cache>>,
+    pub(crate) cache_backend: Option<Arc<dyn CacheBackend>>,
+    pub(crate) quota: Option<Arc<Quota>>,
+    pub(crate) transport: Option<Arc<dyn Transport>>,
+    semaphore: Option<Arc<Semaphore>>,
+    pub(crate) resolve: Vec<(String, SocketAddr)>,
+    pub(crate) max_response_bytes: Option<usize>,
+    pub(crate) plot_defaults: Vec<(Kind, Plot)>,
+    pub(crate) base_url: Option<String>,
+    pub(crate) api_version: Option<String>,
+    timeout: Option<Duration>,
+    retries: u32,
+    retry_backoff: Duration,
+}
+
+impl Default for Http {
+    fn default() -> Http {
+        Http {
+            client: None,
+            limiter: None,
+            cache: None,
+            cache_backend: None,
+            quota: None,
+            transport: None,
+            semaphore: None,
+            resolve: Vec::new(),
+            max_response_bytes: None,
+            plot_defaults: Vec::new(),
+            base_url: None,
+            api_version: None,
+            timeout: None,
+            retries: 0,
+            retry_backoff: RETRY_BACKOFF,
+        }
+    }
+}
+
+impl Http {
+    fn client(&self) -> reqwest::Client {
+        self.client.clone().unwrap_or_else(default_client)
+    }
+
+    /// Gives requests without a client one to share, so the requests
+    /// of a query that sends several reuse its connections.
+    fn share_client(&mut self) {
+        if self.client.is_none() && self.transport.is_none() {
+            self.client = Some(default_client());
+        }
+    }
+
+    /// Points `request` at the address its host resolves to, if it's
+    /// been overridden, keeping the host in the `Host` header.
+    fn resolve(&self, request: &mut reqwest::Request) {
+        let host = match request.url().host_str() {
+            Some(host) => host.to_owned(),
+            None => return,
+        };
+        let addr = match self
+            .resolve
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&host))
+        {
+            Some((_, addr)) => *addr,
+            None => return,
+        };
+
+        let host = match request.url().port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host,
+        };
+        if let Ok(value) = HeaderValue::from_str(&host) {
+            request.headers_mut().insert(HOST, value);
+        }
+
+        let url = request.url_mut();
+        // Neither fails for an http or https URL, which has a host
+        let _ = url.set_ip_host(addr.ip());
+        let _ = url.set_port(Some(addr.port()));
+    }
+
+    /// The base URL requests are sent to, see `endpoint`.
+    fn endpoint(&self) -> String {
+        endpoint(self.base_url.as_deref(), env::var("OMDB_BASE_URL").ok())
+    }
+
+    /// The OMDb API version to request, `1` unless set.
+    fn api_version(&self) -> &str {
+        self.api_version.as_deref().unwrap_or(API_VERSION)
+    }
+
+    /// Waits for the rate limiter, and counts the request against the
+    /// quota, before a request is sent.
+    async fn before_send(&self) -> Result<(), Error> {
+        if let Some(quota) = self.quota.as_ref() {
+            quota.reserve()?;
+        }
+        if let Some(limiter) = self.limiter.as_ref() {
+            limiter.acquire().await;
+        }
+
+        Ok(())
+    }
+
+    /// Uncounts a request from the quota if it failed.
+    fn sent<T>(&self, result: &Result<T, Error>) {
+        if let (Some(quota), Err(_)) = (self.quota.as_ref(), result) {
+            quota.release();
+        }
+    }
+
+    /// The cached response to a request with `params`, if there is one.
+    fn cached(&self, params: &[(&str, String)]) -> Option<Cached> {
+        self.cache.as_ref()?.get(&self.cache_key(params))
+    }
+
+    /// Caches `value` as the response to a request with `params`.
+    fn store(&self, params: &[(&str, String)], value: Cached) {
+        if let Some(cache) = self.cache.as_ref() {
+            cache.insert(self.cache_key(params), value);
+        }
+    }
+
+    /// The cached movie for a request with `params`, from the cache
+    /// or else the `CacheBackend`.
+    fn cached_movie(&self, params: &[(&str, String)]) -> Option<Movie> {
+        if let Some(Cached::Movie(movie)) = self.cached(params) {
+            return Some(*movie);
+        }
+
+        let backend = self.cache_backend.as_ref()?;
+        backend.get(&cache::key_string(&self.cache_key(params)))
+    }
+
+    /// Caches `movie` as the response to a request with `params`, in
+    /// the cache and the `CacheBackend`.
+    fn store_movie(&self, params: &[(&str, String)], movie: &Movie) {
+        self.store(params, Cached::Movie(Box::new(movie.clone())));
+
+        if let Some(backend) = self.cache_backend.as_ref() {
+            backend.put(&cache::key_string(&self.cache_key(params)), movie.clone());
+        }
+    }
+
+    /// The cache key of a request with `params`, which leaves out the
+    /// API key so every key shares the cache.
+    fn cache_key(&self, params: &[(&str, String)]) -> cache::Key {
+        let mut key = vec![
+            (String::from("base_url"), self.endpoint()),
+            (String::from("v"), self.api_version().to_owned()),
+        ];
+        key.extend(
+            params
+                .iter()
+                .filter(|(name, _)| *name != "apikey")
+                .map(|(name, value)| (name.to_string(), value.clone())),
+        );
+        key
+    }
+}
+
+/// The OMDb API version requested by default.
+const API_VERSION: &str = "1";
+
+/// The `User-Agent` sent by clients this crate creates.
+const USER_AGENT: &str = concat!("omdb-rs/", env!("CARGO_PKG_VERSION"));
+
+/// A new `reqwest::Client` identifying itself as this crate.
+pub(crate) fn default_client() -> reqwest::Client {
+    default_client_builder().build().unwrap_or_default()
+}
+
+/// A builder for clients identifying themselves as this crate.
+pub(crate) fn default_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder().user_agent(USER_AGENT)
+}
+
+impl PartialEq for Http {
+    fn eq(&self, other: &Http) -> bool {
+        self.api_version() == other.api_version() && self.plot_defaults == other.plot_defaults
+    }
+}
+
+impl Eq for Http {}
+
+impl Hash for Http {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // `v=1` is the same query whether it's set or not
+        self.api_version().hash(state);
+        self.plot_defaults.hash(state);
+    }
+}
+
+/// Builds a request to OMDb with the given parameters and extra headers.
+fn build_request<I, K, V>(
+    http: &Http,
+    params: I,
+    headers: &[(String, String)],
+) -> reqwest::RequestBuilder
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)> + Serialize,
+    K: AsRef<str> + Serialize,
+    V: AsRef<str> + Serialize,
+{
     let params = params.into_iter().collect::<Vec<_>>();
 
-    let response = reqwest::Client::new()
-        .get(API_ENDPOINT)
-        .query(&[("v", API_VERSION)])
+    let mut request = http
+        .client()
+        .get(&http.endpoint())
+        .query(&[("v", http.api_version())])
         .query(&[("r", "json")])
-        .query(&params)
-        .send()
-        .await?;
+        .query(&params);
+
+    if let Some(timeout) = http.timeout {
+        request = request.timeout(timeout);
+    }
+
+    for (name, value) in headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+
+    request
+}
+
+/// A description of the request a query would send, without sending it.
+///
+/// Created with `FindQuery::plan` or `SearchQuery::plan`.
+/// The API key is always redacted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestPlan {
+    /// The base URL the request is sent to.
+    pub base_url: String,
+    /// The full URL of the request.
+    pub url: String,
+    /// The query parameters of the request.
+    pub params: Vec<(String, String)>,
+    /// Extra headers sent with the request.
+    pub headers: Vec<(String, String)>,
+    /// The OMDb API version requested.
+    pub api_version: String,
+    /// How long the request may take, if it's limited.
+    pub timeout: Option<Duration>,
+    /// How many times a failed request is retried.
+    pub retries: u32,
+    /// The delay before the first retry, see `FindQuery::retry_backoff`.
+    pub retry_backoff: Duration,
+}
+
+/// Describes the request for the given parameters and extra headers.
+fn plan(
+    http: &Http,
+    params: Vec<(&str, String)>,
+    headers: &[(String, String)],
+) -> Result<RequestPlan, Error> {
+    let params: Vec<(String, String)> = params
+        .into_iter()
+        .map(|(key, value)| match key {
+            "apikey" => (key.to_owned(), String::from("REDACTED")),
+            _ => (key.to_owned(), value),
+        })
+        .collect();
+
+    let request = build_request(http, &params, headers).build()?;
+
+    Ok(RequestPlan {
+        base_url: http.endpoint(),
+        url: request.url().to_string(),
+        params,
+        headers: headers.to_vec(),
+        api_version: http.api_version().to_owned(),
+        timeout: http.timeout,
+        retries: http.retries,
+        retry_backoff: http.retry_backoff,
+    })
+}
+
+/// Decodes the JSON body of a response from OMDb.
+///
+/// Some requests with an invalid API key get an HTML page back rather
+/// than JSON, which is reported as `Error::InvalidApiKey`. Any other
+/// HTML page is an `Error::Decode`, like a body that isn't valid JSON.
+/// A body longer than `max_bytes` is an `Error::Other`, without reading
+/// the rest of it.
+async fn decode<T: DeserializeOwned>(
+    response: reqwest::Response,
+    max_bytes: Option<usize>,
+) -> Result<T, Error> {
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+
+    let body = match max_bytes {
+        Some(max) => read_limited(response, max).await?,
+        None => response.text().await?,
+    };
+
+    decode_body(&body, is_html)
+}
+
+/// The body of `response`, or `Error::Other` once it's longer than
+/// `max` bytes.
+async fn read_limited(mut response: reqwest::Response, max: usize) -> Result<String, Error> {
+    let too_large = Error::Other("response too large");
+    if response
+        .content_length()
+        .is_some_and(|len| len > max as u64)
+    {
+        return Err(too_large);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max {
+            return Err(too_large);
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Decodes the body of a response from OMDb, which is JSON unless
+/// it's HTML.
+fn decode_body<T: DeserializeOwned>(body: &str, is_html: bool) -> Result<T, Error> {
+    if is_html && body.to_lowercase().contains("invalid api key") {
+        return Err(Error::InvalidApiKey);
+    }
+
+    // Some proxies prepend a byte order mark
+    let body = body.trim_start_matches('\u{feff}');
+
+    serde_json::from_str(body).map_err(|err| Error::decode(body, err))
+}
+
+/// The API key to send, `apikey` if it's set or `env_apikey`, the
+/// value of `OMDB_APIKEY`, if not.
+fn default_apikey(apikey: Option<&str>, env_apikey: Option<String>) -> Option<String> {
+    apikey.map(str::to_owned).or(env_apikey)
+}
+
+/// Checks that an API key looks like one issued by OMDb.
+///
+/// A key must be set. When `strict`, it must also look like one of
+/// OMDb's keys, which are 8 hexadecimal characters, e.g. `1a2b3c4d`.
+fn check_apikey(apikey: Option<&str>, strict: bool) -> Result<(), Error> {
+    let key = apikey.ok_or(Error::MissingApiKey)?;
+
+    if strict && (key.len() != 8 || !key.chars().all(|c| c.is_ascii_hexdigit())) {
+        return Err(Error::Other("malformed API key"));
+    }
+
+    Ok(())
+}
+
+/// Checks that `id` looks like an IMDb title ID, such as `tt0032138`.
+///
+/// IMDb has lengthened its IDs before, so any number of digits from 7
+/// up is accepted.
+fn check_imdb_id(id: &str) -> Result<(), Error> {
+    let valid = id
+        .strip_prefix("tt")
+        .is_some_and(|digits| digits.len() >= 7 && digits.chars().all(|c| c.is_ascii_digit()));
+
+    if !valid {
+        return Err(Error::InvalidImdbId(id.to_owned()));
+    }
+
+    Ok(())
+}
 
-    let status = response.status();
+/// Turns OMDb's response to a find request into a `Movie`.
+fn find_result(response: FindResponse) -> Result<Movie, Error> {
+    // Check if the Api's Response string equals true
+    if response.response.to_lowercase() != "true" {
+        // Return with the Api's Error field or "undefined" if empty
+        return Err(api_error(response.error.as_deref().unwrap_or("undefined")));
+    }
 
-    if !status.is_success() {
-        return Err(Error::Status(status));
+    // A successful response should always describe a title, seasons
+    // are the only responses without their own IMDb ID. "N/A" becomes
+    // an empty string, so it's as good as missing.
+    let missing = |value: &Option<String>| {
+        value
+            .as_deref()
+            .is_none_or(|value| value.trim().is_empty() || value == "N/A")
+    };
+    if missing(&response.title) || (missing(&response.imdb_id) && response.episodes.is_none()) {
+        return Err(Error::Other("malformed successful response"));
     }
 
-    Ok(response)
+    Ok(response.into())
+}
+
+/// Converts a response to a season request into a `Season`.
+fn season_result(response: FindResponse) -> Result<Season, Error> {
+    let parse = |value: Option<&str>| value.and_then(|value| value.parse().ok());
+    let season = parse(response.season.as_deref());
+    let total_seasons = parse(response.total_seasons.as_deref());
+
+    let movie = find_result(response)?;
+    match (season, total_seasons) {
+        (Some(season), Some(total_seasons)) if !movie.episodes.is_empty() => Ok(Season {
+            title: movie.title,
+            season,
+            total_seasons,
+            episodes: movie.episodes,
+        }),
+        _ => Err(Error::Other("response is not a season")),
+    }
 }
 
 /// Starts a new `FindQuery` with an imdb_id.
@@ -97,6 +696,33 @@ pub fn title<S: Into<String>>(title: S) -> FindQuery {
     }
 }
 
+/// Starts a new `FindQuery` with either an IMDb ID or a title.
+///
+/// Strings that are `tt` followed only by digits, like `tt0032138`,
+/// are treated as IMDb IDs. Anything else is treated as a title.
+/// # Examples
+///
+/// ```
+/// let by_id = omdb::find("tt0032138");
+/// let by_title = omdb::find("The Wizard of Oz");
+///
+/// assert!(by_id.params()[0].0 == "i");
+/// assert!(by_title.params()[0].0 == "t");
+/// ```
+pub fn find<S: Into<String>>(id_or_title: S) -> FindQuery {
+    let id_or_title = id_or_title.into();
+
+    let is_imdb_id = id_or_title
+        .strip_prefix("tt")
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()));
+
+    if is_imdb_id {
+        imdb_id(id_or_title)
+    } else {
+        title(id_or_title)
+    }
+}
+
 /// Starts a new `SearchQuery`.
 ///
 /// This can be built upon to add other constraints while
@@ -122,36 +748,155 @@ pub fn search<S: Into<String>>(search: S) -> SearchQuery {
     }
 }
 
+/// Starts a new `BatchQuery` looking up each of `ids` by IMDb ID.
+///
+/// OMDb has no batch endpoint, so each ID is its own request, with
+/// several in flight at once.
+/// # Examples
+///
+/// ```
+/// # async fn test() {
+///     let apikey = std::env::var("OMDB_APIKEY").expect("OMDB_APIKEY must be set");
+///     let movies = omdb::imdb_ids(vec!["tt0032138", "tt2575988"])
+///         .apikey(apikey)
+///         .get()
+///         .await;
+///
+///     assert!(movies[0].as_ref().unwrap().title == "The Wizard of Oz");
+/// # }
+/// ```
+pub fn imdb_ids<I>(ids: I) -> BatchQuery
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    BatchQuery {
+        ids: ids.into_iter().map(Into::into).collect(),
+        query: FindQuery::default(),
+        concurrency: BATCH_CONCURRENCY,
+    }
+}
+
 /// Represents a query being bulit for OMDb.
 /// Follows the Builder pattern.
-#[derive(Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct FindQuery {
     // One required
     imdb_id: Option<String>,
     title: Option<String>,
 
     apikey: Option<String>,
+    strict_apikey: bool,
 
     // Optional
     kind: Option<Kind>,
     year: Option<String>,
-    plot: Option<Plot>, // TODO: Season and Episode
+    plot: Option<Plot>,
+    plot_fallback: bool,
+    exact: bool,
+    season: Option<u16>,
+    episode: Option<u16>,
+
+    // Passed through as-is
+    extra: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+
+    http: Http,
 }
 
-impl Default for FindQuery {
-    fn default() -> FindQuery {
-        FindQuery {
-            imdb_id: None,
-            title: None,
-            apikey: None,
-            kind: None,
-            year: None,
-            plot: None,
+impl FindQuery {
+    /// Apply builder methods to an owned query, returning it.
+    ///
+    /// The builder methods take `&mut self`, so this makes it easy to
+    /// store a query or build one up conditionally.
+    /// # Examples
+    ///
+    /// ```
+    /// use omdb::Kind;
+    ///
+    /// let with_year = true;
+    /// let mut query = omdb::title("Batman").with(|q| q.apikey("1a2b3c4d").kind(Kind::Movie));
+    /// if with_year {
+    ///     query = query.with(|q| q.year(1989));
+    /// }
+    ///
+    /// assert!(query.params().contains(&("y", String::from("1989"))));
+    /// ```
+    pub fn with<F>(mut self, build: F) -> FindQuery
+    where
+        F: FnOnce(&mut FindQuery) -> &mut FindQuery,
+    {
+        build(&mut self);
+        self
+    }
+
+    /// Build a `FindQuery` from an OMDb-style query string,
+    /// such as `t=batman&y=1989&type=movie`.
+    ///
+    /// This is the inverse of `params`. Known parameters are parsed
+    /// into their builder fields and anything else is kept as a `param`.
+    /// # Examples
+    ///
+    /// ```
+    /// let query = omdb::query::FindQuery::from_query_string("t=batman&y=1989&type=movie").unwrap();
+    ///
+    /// assert!(query.params().contains(&("type", String::from("movie"))));
+    /// ```
+    pub fn from_query_string(query: &str) -> Result<FindQuery, Error> {
+        let pairs: Vec<(String, String)> = serde_urlencoded::from_str(query)
+            .map_err(|_| Error::Other("malformed query string"))?;
+
+        let mut find = FindQuery::default();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "i" => find.imdb_id = Some(value),
+                "t" => find.title = Some(value),
+                "apikey" => find.apikey = Some(value),
+                "type" => {
+                    find.kind = Some(
+                        value
+                            .parse()
+                            .map_err(|_| Error::Other("invalid `type` parameter"))?,
+                    )
+                }
+                "y" => find.year = Some(value),
+                "plot" => {
+                    find.plot(
+                        value
+                            .parse()
+                            .map_err(|_| Error::Other("invalid `plot` parameter"))?,
+                    );
+                }
+                "Season" => {
+                    find.season = Some(
+                        value
+                            .parse()
+                            .map_err(|_| Error::Other("invalid `Season` parameter"))?,
+                    )
+                }
+                "Episode" => {
+                    find.episode = Some(
+                        value
+                            .parse()
+                            .map_err(|_| Error::Other("invalid `Episode` parameter"))?,
+                    )
+                }
+                "v" => {
+                    find.api_version(value);
+                }
+                // Always sent by `get_request`
+                "r" => {}
+                _ => find.extra.push((key, value)),
+            }
+        }
+
+        if find.imdb_id.is_none() && find.title.is_none() {
+            return Err(Error::Other("query string needs an `i` or `t` parameter"));
         }
+
+        Ok(find)
     }
-}
 
-impl FindQuery {
     /// Specify the kind of media.
     pub fn kind(&mut self, kind: Kind) -> &mut FindQuery {
         self.kind = Some(kind);
@@ -164,20 +909,131 @@ impl FindQuery {
         self
     }
 
+    /// Specify a season of a series.
+    ///
+    /// On its own, the season's episodes are fetched into
+    /// `Movie::episodes`. Along with `episode`, that single episode is
+    /// fetched instead.
+    pub fn season(&mut self, season: u16) -> &mut FindQuery {
+        self.season = Some(season);
+        self
+    }
+
+    /// Specify an episode of the `season`.
+    pub fn episode(&mut self, episode: u16) -> &mut FindQuery {
+        self.episode = Some(episode);
+        self
+    }
+
     pub fn apikey<S: ToString>(&mut self, apikey: S) -> &mut FindQuery {
         self.apikey = Some(apikey.to_string());
         self
     }
 
+    /// Check the API key's format locally before sending the request.
+    ///
+    /// OMDb keys are 8 hexadecimal characters. Any other key fails
+    /// with `Error::Other` without reaching OMDb. Off by default.
+    pub fn strict_apikey(&mut self, strict: bool) -> &mut FindQuery {
+        self.strict_apikey = strict;
+        self
+    }
+
+    /// Send `user_agent` as the `User-Agent` header.
+    ///
+    /// Without this, requests identify themselves as `omdb-rs/<version>`,
+    /// unless they're sent with a client given to `with_client`, which
+    /// keeps its own `User-Agent`.
+    pub fn user_agent<S: Into<String>>(&mut self, user_agent: S) -> &mut FindQuery {
+        self.headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("User-Agent"));
+        self.headers
+            .push((String::from("User-Agent"), user_agent.into()));
+        self
+    }
+
+    /// Send an `X-Request-Id` header, for tracing the request.
+    ///
+    /// This replaces any request ID set before.
+    pub fn request_id(&mut self, id: &str) -> &mut FindQuery {
+        self.headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("X-Request-Id"));
+        self.headers
+            .push((String::from("X-Request-Id"), id.to_owned()));
+        self
+    }
+
+    /// Send extra headers along with the request.
+    pub fn headers(&mut self, headers: HeaderMap) -> &mut FindQuery {
+        for (name, value) in headers.iter() {
+            self.headers.push((
+                name.as_str().to_owned(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            ));
+        }
+        self
+    }
+
     /// Specify the plot length.
+    ///
+    /// `Plot::Default` clears a plot length set earlier. A query from an
+    /// `omdb::Client` without a plot length uses the client's default
+    /// for the query's `kind`, see `Client::plot_for`.
     pub fn plot(&mut self, plot: Plot) -> &mut FindQuery {
-        self.plot = Some(plot);
+        self.plot = match plot {
+            Plot::Default => None,
+            plot => Some(plot),
+        };
         self
     }
 
-    /// Perform OMDb Api request and attempt to find the movie
-    /// this `FindQuery` is describing.
-    pub async fn get(&self) -> Result<Movie, Error> {
+    /// Fail with `ApiError::MultipleResults` when more than one title
+    /// matches, rather than letting OMDb pick one.
+    ///
+    /// This searches for the title first, with the same kind and year,
+    /// and counts the results on the first page with exactly the same
+    /// title, ignoring case. It has no effect when finding by IMDb ID.
+    /// Defaults to `false`.
+    pub fn exact(&mut self, exact: bool) -> &mut FindQuery {
+        self.exact = exact;
+        self
+    }
+
+    /// Retry with `Plot::Short` when a `Plot::Full` request comes back
+    /// without a plot.
+    ///
+    /// OMDb has no full plot for some obscure titles. With this set,
+    /// `get` makes at most one extra request to fetch the short plot
+    /// instead. If that request fails, the movie without a plot is
+    /// returned. Defaults to `false`.
+    pub fn plot_fallback(&mut self, fallback: bool) -> &mut FindQuery {
+        self.plot_fallback = fallback;
+        self
+    }
+
+    /// Add an arbitrary query parameter, sent to OMDb as-is.
+    pub fn param<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> &mut FindQuery {
+        self.extra.push((key.into(), value.into()));
+        self
+    }
+
+    /// The plot length to ask for, from `plot` or else the default for
+    /// the query's `kind`.
+    fn resolved_plot(&self) -> Option<Plot> {
+        let plot = self.plot.or_else(|| {
+            let kind = self.kind?;
+            self.http
+                .plot_defaults
+                .iter()
+                .find(|(default_kind, _)| *default_kind == kind)
+                .map(|(_, plot)| *plot)
+        });
+
+        plot.filter(|plot| *plot != Plot::Default)
+    }
+
+    /// The query parameters this `FindQuery` sends to OMDb.
+    pub fn params(&self) -> Vec<(&str, String)> {
         let mut params: Vec<(&str, String)> = Vec::new();
 
         if let Some(i) = self.imdb_id.as_ref() {
@@ -199,57 +1055,474 @@ impl FindQuery {
             params.push(("y", year.clone()));
         }
 
-        if let Some(plot) = self.plot.as_ref() {
-            let p: &str = (*plot).into();
+        if let Some(plot) = self.resolved_plot() {
+            let p: &str = plot.into();
             params.push(("plot", String::from(p)));
         }
 
-        // Send our request
-        let response: FindResponse = get_request(params).await?.json().await?;
+        if let Some(season) = self.season {
+            params.push(("Season", season.to_string()));
+        }
 
-        // Check if the Api's Response string equals true
-        if response.response.to_lowercase() != "true" {
-            // Return with the Api's Error field or "undefined" if empty
-            return Err(Error::Api(
-                response.error.unwrap_or_else(|| "undefined".to_owned()),
-            ));
+        if let Some(episode) = self.episode {
+            params.push(("Episode", episode.to_string()));
+        }
+
+        for (key, value) in self.extra.iter() {
+            params.push((key, value.clone()));
         }
 
-        Ok(response.into())
+        params
     }
-}
 
-/// Represents a query being bulit for OMDb.
-/// Follows the Builder pattern.
-#[derive(Debug)]
-pub struct SearchQuery {
-    search: String,
-    apikey: Option<String>,
+    /// Describe the request `get` would send, without sending it.
+    pub fn plan(&self) -> Result<RequestPlan, Error> {
+        plan(&self.http, self.params(), &self.headers)
+    }
 
-    // Optional
-    kind: Option<Kind>,
-    year: Option<String>,
-    page: Option<usize>,
-}
+    /// Perform OMDb Api request and attempt to find the movie
+    /// this `FindQuery` is describing.
+    ///
+    /// Without an `apikey`, the `OMDB_APIKEY` environment variable is used.
+    pub async fn get(&self) -> Result<Movie, Error> {
+        let query = self.checked()?;
+        query.check_episode()?;
+
+        if query.exact {
+            query.check_unambiguous().await?;
+        }
+
+        fetch_with_plot_fallback(&query, |query| async move {
+            let params = query.params();
+            if let Some(movie) = query.http.cached_movie(&params) {
+                return Ok(movie);
+            }
+
+            // Send our request
+            let response: FindResponse = send(&query.http, params.clone(), &query.headers).await?;
+
+            let movie = find_result(response)?;
+            query.http.store_movie(&params, &movie);
+
+            Ok(movie)
+        })
+        .await
+    }
+
+    /// Perform the same request as `get`, returning `None` if OMDb
+    /// couldn't find the title.
+    ///
+    /// Only OMDb's `Movie not found!` and `Series or Episode not found!`
+    /// errors become `None`, any other error is returned as-is.
+    pub async fn get_optional(&self) -> Result<Option<Movie>, Error> {
+        match self.get().await {
+            Ok(movie) => Ok(Some(movie)),
+            Err(Error::Api(ApiError::NotFound))
+            | Err(Error::Api(ApiError::SeriesOrEpisodeNotFound)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Perform the same request as `get`, also returning OMDb's response
+    /// as it was sent.
+    ///
+    /// This gives access to fields `Movie` doesn't have. Unlike `get`,
+    /// this never retries with a short plot, so the `Movie` always
+    /// comes from the returned JSON.
+    pub async fn get_with_raw(&self) -> Result<(Movie, serde_json::Value), Error> {
+        let query = self.checked()?;
+        query.check_episode()?;
+
+        if query.exact {
+            query.check_unambiguous().await?;
+        }
+
+        let raw: serde_json::Value = send(&query.http, query.params(), &query.headers).await?;
+        let response = serde_json::from_value(raw.clone())
+            .map_err(|err| Error::decode(&raw.to_string(), err))?;
+        let movie = find_result(response)?;
+
+        Ok((movie, raw))
+    }
+
+    /// Perform OMDb Api request for the season this `FindQuery` is
+    /// describing, and return its episodes.
+    ///
+    /// The query needs a `season` and no `episode`.
+    pub async fn get_season(&self) -> Result<Season, Error> {
+        let query = self.checked()?;
+        query.check_episode()?;
+
+        if query.season.is_none() || query.episode.is_some() {
+            return Err(Error::Other("get_season needs a season and no episode"));
+        }
+
+        let response: FindResponse = send(&query.http, query.params(), &query.headers).await?;
+
+        season_result(response)
+    }
+
+    /// Fetch the episodes of every season of this series, in order.
+    ///
+    /// The series is fetched first to find its `total_seasons`, then
+    /// each season in turn. An error fetching any of them is returned.
+    pub async fn get_all_episodes(&self) -> Result<Vec<Episode>, Error> {
+        let query = self.checked()?;
+
+        collect_episodes(&query, |query| async move { query.get().await }).await
+    }
+
+    /// A copy of this query with a checked API key, defaulting to
+    /// `OMDB_APIKEY`, and a checked IMDb ID.
+    fn checked(&self) -> Result<FindQuery, Error> {
+        self.checked_with(env::var("OMDB_APIKEY").ok())
+    }
 
-impl Default for SearchQuery {
-    fn default() -> SearchQuery {
-        SearchQuery {
-            search: String::new(),
-            apikey: None,
-            kind: None,
-            year: None,
-            page: None,
+    /// Like `checked`, with `env_apikey` in place of `OMDB_APIKEY`.
+    fn checked_with(&self, env_apikey: Option<String>) -> Result<FindQuery, Error> {
+        if let Some(id) = self.imdb_id.as_ref() {
+            check_imdb_id(id)?;
         }
+
+        let mut query = self.clone();
+        query.apikey = default_apikey(self.apikey.as_deref(), env_apikey);
+        check_apikey(query.apikey.as_deref(), query.strict_apikey)?;
+
+        Ok(query)
+    }
+
+    /// Checks that no more than one title matches this query's title.
+    async fn check_unambiguous(&self) -> Result<(), Error> {
+        let title = match (self.imdb_id.as_ref(), self.title.as_ref()) {
+            (None, Some(title)) => title,
+            _ => return Ok(()),
+        };
+
+        let search = SearchQuery {
+            search: title.clone(),
+            apikey: self.apikey.clone(),
+            kind: self.kind,
+            year: self.year.clone(),
+            headers: self.headers.clone(),
+            http: self.http.clone(),
+            ..Default::default()
+        };
+
+        let results = match search.get().await {
+            Ok(results) => results.results,
+            // Leave it to the find request to report
+            Err(Error::Api(ApiError::NotFound)) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        let title = title.to_lowercase();
+        let matches = results
+            .iter()
+            .filter(|movie| movie.title.to_lowercase() == title)
+            .count();
+
+        if matches > 1 {
+            return Err(Error::Api(ApiError::MultipleResults));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `season` and `episode` make sense for this query.
+    fn check_episode(&self) -> Result<(), Error> {
+        if self.season.is_none() && self.episode.is_none() {
+            return Ok(());
+        }
+
+        if self.kind.is_some() && self.kind != Some(Kind::Series) {
+            return Err(Error::Other("season and episode need Kind::Series"));
+        }
+        if self.season.is_none() {
+            return Err(Error::Other("episode needs a season"));
+        }
+
+        Ok(())
+    }
+
+    /// Perform the same request as `get`, using `key` as the API key.
+    ///
+    /// The key is only used for this request, the stored `apikey`
+    /// is left unchanged.
+    pub async fn get_with_key(&self, key: &str) -> Result<Movie, Error> {
+        self.with_key(key).get().await
+    }
+
+    /// Send this query with `client` instead of a new `reqwest::Client`.
+    ///
+    /// Reusing a client keeps its connection pool between queries, and
+    /// lets it be configured, e.g. with a proxy or default headers.
+    pub fn with_client(&mut self, client: reqwest::Client) -> &mut FindQuery {
+        self.http.client = Some(client);
+        self
+    }
+
+    /// Send requests through `transport` instead of `reqwest`.
+    ///
+    /// See `Transport`.
+    pub fn transport(&mut self, transport: Arc<dyn Transport>) -> &mut FindQuery {
+        self.http.transport = Some(transport);
+        self
+    }
+
+    /// How this query's requests are sent, for an `omdb::Client` to
+    /// share its settings.
+    pub(crate) fn http_mut(&mut self) -> &mut Http {
+        &mut self.http
+    }
+
+    /// Send requests to `base_url` instead of OMDb, such as a proxy
+    /// or a mock server.
+    ///
+    /// This takes precedence over `OMDB_BASE_URL`.
+    pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut FindQuery {
+        self.http.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Request version `api_version` of OMDb's API, sent as the `v`
+    /// parameter.
+    ///
+    /// This defaults to `1`, the current version. Responses are always
+    /// requested as JSON.
+    pub fn api_version<S: Into<String>>(&mut self, api_version: S) -> &mut FindQuery {
+        self.http.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Give up on requests that take longer than `timeout`.
+    ///
+    /// A request that times out fails with an `Error::Http`. Requests
+    /// have no timeout by default.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut FindQuery {
+        self.http.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry requests that fail with a server error, a timeout or a
+    /// connection error up to `retries` times. Defaults to 0.
+    ///
+    /// Errors from OMDb itself, such as `Movie not found!`, aren't
+    /// retried, since they'd happen again.
+    pub fn retries(&mut self, retries: u32) -> &mut FindQuery {
+        self.http.retries = retries;
+        self
+    }
+
+    /// The delay before the first retry, which doubles with each
+    /// retry after it, up to a minute. Defaults to 250 milliseconds.
+    ///
+    /// Each delay is randomly shortened by up to half.
+    pub fn retry_backoff(&mut self, base: Duration) -> &mut FindQuery {
+        self.http.retry_backoff = base;
+        self
+    }
+
+    /// A copy of this query using a different API key.
+    fn with_key(&self, key: &str) -> FindQuery {
+        let mut query = self.clone();
+        query.apikey(key);
+        query
+    }
+
+    /// Perform the same request as `get`, converting the movie into `T`.
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn test() {
+    ///     struct Film {
+    ///         name: String,
+    ///     }
+    ///
+    ///     impl From<omdb::Movie> for Film {
+    ///         fn from(movie: omdb::Movie) -> Film {
+    ///             Film { name: movie.title }
+    ///         }
+    ///     }
+    ///
+    ///     let apikey = std::env::var("OMDB_APIKEY").expect("OMDB_APIKEY must be set");
+    ///     let film: Film = omdb::imdb_id("tt0032138")
+    ///         .apikey(apikey)
+    ///         .get_into()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     assert!(film.name == "The Wizard of Oz");
+    /// # }
+    /// ```
+    pub async fn get_into<T: From<Movie>>(&self) -> Result<T, Error> {
+        Ok(self.get().await?.into())
+    }
+
+    /// Perform the same request as `get`, also returning how long it took.
+    ///
+    /// The duration is the wall-clock time of the whole call.
+    pub async fn get_timed(&self) -> Result<(Movie, Duration), Error> {
+        let start = Instant::now();
+        let movie = self.get().await?;
+
+        Ok((movie, start.elapsed()))
     }
 }
 
+/// Represents a query being bulit for OMDb.
+/// Follows the Builder pattern.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SearchQuery {
+    search: String,
+    apikey: Option<String>,
+    strict_apikey: bool,
+
+    // Optional
+    kind: Option<Kind>,
+    year: Option<String>,
+    year_range: Option<(u16, u16)>,
+    page: Option<usize>,
+
+    // Stored as bits so the query stays `Eq` and `Hash`
+    min_title_similarity: Option<u32>,
+    retry_normalized: bool,
+
+    headers: Vec<(String, String)>,
+
+    http: Http,
+}
+
 impl SearchQuery {
+    /// Apply builder methods to an owned query, returning it.
+    ///
+    /// See `FindQuery::with`.
+    pub fn with<F>(mut self, build: F) -> SearchQuery
+    where
+        F: FnOnce(&mut SearchQuery) -> &mut SearchQuery,
+    {
+        build(&mut self);
+        self
+    }
+
     pub fn apikey<S: ToString>(&mut self, apikey: S) -> &mut SearchQuery {
         self.apikey = Some(apikey.to_string());
         self
     }
 
+    /// Send this query with `client` instead of a new `reqwest::Client`.
+    ///
+    /// Reusing a client keeps its connection pool between queries, and
+    /// lets it be configured, e.g. with a proxy or default headers.
+    pub fn with_client(&mut self, client: reqwest::Client) -> &mut SearchQuery {
+        self.http.client = Some(client);
+        self
+    }
+
+    /// Send requests through `transport` instead of `reqwest`.
+    ///
+    /// See `Transport`.
+    pub fn transport(&mut self, transport: Arc<dyn Transport>) -> &mut SearchQuery {
+        self.http.transport = Some(transport);
+        self
+    }
+
+    /// How this query's requests are sent, for an `omdb::Client` to
+    /// share its settings.
+    pub(crate) fn http_mut(&mut self) -> &mut Http {
+        &mut self.http
+    }
+
+    /// Send requests to `base_url` instead of OMDb, such as a proxy
+    /// or a mock server.
+    ///
+    /// This takes precedence over `OMDB_BASE_URL`.
+    pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut SearchQuery {
+        self.http.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Request version `api_version` of OMDb's API, sent as the `v`
+    /// parameter.
+    ///
+    /// This defaults to `1`, the current version. Responses are always
+    /// requested as JSON.
+    pub fn api_version<S: Into<String>>(&mut self, api_version: S) -> &mut SearchQuery {
+        self.http.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Give up on requests that take longer than `timeout`.
+    ///
+    /// A request that times out fails with an `Error::Http`. Requests
+    /// have no timeout by default.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut SearchQuery {
+        self.http.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry requests that fail with a server error, a timeout or a
+    /// connection error up to `retries` times. Defaults to 0.
+    ///
+    /// Errors from OMDb itself, such as `Movie not found!`, aren't
+    /// retried, since they'd happen again.
+    pub fn retries(&mut self, retries: u32) -> &mut SearchQuery {
+        self.http.retries = retries;
+        self
+    }
+
+    /// The delay before the first retry, which doubles with each
+    /// retry after it, up to a minute. Defaults to 250 milliseconds.
+    ///
+    /// Each delay is randomly shortened by up to half.
+    pub fn retry_backoff(&mut self, base: Duration) -> &mut SearchQuery {
+        self.http.retry_backoff = base;
+        self
+    }
+
+    /// Check the API key's format locally before sending the request.
+    ///
+    /// OMDb keys are 8 hexadecimal characters. Any other key fails
+    /// with `Error::Other` without reaching OMDb. Off by default.
+    pub fn strict_apikey(&mut self, strict: bool) -> &mut SearchQuery {
+        self.strict_apikey = strict;
+        self
+    }
+
+    /// Send `user_agent` as the `User-Agent` header.
+    ///
+    /// Without this, requests identify themselves as `omdb-rs/<version>`,
+    /// unless they're sent with a client given to `with_client`, which
+    /// keeps its own `User-Agent`.
+    pub fn user_agent<S: Into<String>>(&mut self, user_agent: S) -> &mut SearchQuery {
+        self.headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("User-Agent"));
+        self.headers
+            .push((String::from("User-Agent"), user_agent.into()));
+        self
+    }
+
+    /// Send an `X-Request-Id` header, for tracing the request.
+    ///
+    /// This replaces any request ID set before.
+    pub fn request_id(&mut self, id: &str) -> &mut SearchQuery {
+        self.headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("X-Request-Id"));
+        self.headers
+            .push((String::from("X-Request-Id"), id.to_owned()));
+        self
+    }
+
+    /// Send extra headers along with the request.
+    pub fn headers(&mut self, headers: HeaderMap) -> &mut SearchQuery {
+        for (name, value) in headers.iter() {
+            self.headers.push((
+                name.as_str().to_owned(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            ));
+        }
+        self
+    }
+
     /// Specify the kind of media.
     pub fn kind(&mut self, kind: Kind) -> &mut SearchQuery {
         self.kind = Some(kind);
@@ -258,8 +1531,27 @@ impl SearchQuery {
     }
 
     /// Specify the year.
+    ///
+    /// This replaces any `year_range`.
     pub fn year<S: ToString>(&mut self, year: S) -> &mut SearchQuery {
         self.year = Some(year.to_string());
+        self.year_range = None;
+        self
+    }
+
+    /// Only find results from `start` to `end`, inclusive.
+    ///
+    /// OMDb can only search one year at a time, so this sends a search
+    /// for every year in the range, costing one request per year for
+    /// `get` and `count`, and one per page of every year for `get_all`.
+    /// Results are merged in year order, without duplicate IMDb IDs, and
+    /// `total_results` is the sum over every year. Years without any
+    /// results are skipped.
+    ///
+    /// This replaces any `year`.
+    pub fn year_range(&mut self, start: u16, end: u16) -> &mut SearchQuery {
+        self.year_range = Some((start, end));
+        self.year = None;
         self
     }
 
@@ -269,9 +1561,20 @@ impl SearchQuery {
         self
     }
 
-    /// Perform OMDb Api request and attempt to find the movie
-    /// this `FindQuery` is describing.
-    pub async fn get(&self) -> Result<SearchResults, Error> {
+    /// Drop results whose title is less similar to the search than `min`.
+    ///
+    /// Similarity ranges from `0.0` (nothing in common) to `1.0` (the same
+    /// title, ignoring case). The filtering happens after fetching, so it
+    /// shrinks `results` but `total_results` stays as OMDb reported it.
+    /// Paging is unaffected, pages and ranges are counted in OMDb's
+    /// results before any are dropped.
+    pub fn min_title_similarity(&mut self, min: f32) -> &mut SearchQuery {
+        self.min_title_similarity = Some(min.to_bits());
+        self
+    }
+
+    /// The query parameters this `SearchQuery` sends to OMDb.
+    pub fn params(&self) -> Vec<(&str, String)> {
         let mut params: Vec<(&str, String)> = Vec::new();
 
         params.push(("s", self.search.clone()));
@@ -293,17 +1596,2506 @@ impl SearchQuery {
             params.push(("page", page.to_string()));
         }
 
-        // Send our request
-        let response: SearchResponse = get_request(params).await?.json().await?;
+        params
+    }
 
-        // Check if the Api's Response string equals true
-        if response.response.to_lowercase() != "true" {
-            // Return with the Api's Error field or "undefined" if empty
-            return Err(Error::Api(
-                response.error.unwrap_or_else(|| "undefined".to_owned()),
-            ));
+    /// Retry searches that find nothing with a tidied up search term.
+    ///
+    /// The term is tidied by collapsing letters repeated in a row, so
+    /// `Battmann` becomes `Batman`, and trimming punctuation from the end.
+    /// If that changes the term it's searched for instead, costing one
+    /// extra request. Off by default.
+    pub fn retry_normalized(&mut self, retry: bool) -> &mut SearchQuery {
+        self.retry_normalized = retry;
+        self
+    }
+
+    /// Describe the request `get` would send, without sending it.
+    pub fn plan(&self) -> Result<RequestPlan, Error> {
+        plan(&self.http, self.params(), &self.headers)
+    }
+
+    /// Perform OMDb Api request and attempt to find the movie
+    /// this `FindQuery` is describing.
+    ///
+    /// Without an `apikey`, the `OMDB_APIKEY` environment variable is used.
+    pub async fn get(&self) -> Result<SearchResults, Error> {
+        let mut results = match self.year_queries()? {
+            Some(queries) => {
+                let mut results =
+                    merge_years(queries, |query| async move { query.get_page().await }).await?;
+                results.page = self.page.unwrap_or(1);
+                results
+            }
+            None => self.get_page().await?,
+        };
+
+        self.retain_similar(&mut results.results);
+
+        Ok(results)
+    }
+
+    /// Perform the request for `get`, for at most a single year,
+    /// without dropping results for `min_title_similarity`.
+    async fn get_page(&self) -> Result<SearchResults, Error> {
+        let query = self.checked()?;
+
+        // OMDb's pages start at 1
+        if query.page == Some(0) {
+            return Err(Error::Other("page must be >= 1"));
+        }
+
+        let mut result = search_request(&query.http, query.params(), &query.headers).await;
+
+        if query.retry_normalized && is_empty_search(&result) {
+            let normalized = normalize_search(&query.search);
+
+            if normalized != query.search {
+                let mut query = query.clone();
+                query.search = normalized;
+                result = search_request(&query.http, query.params(), &query.headers).await;
+            }
+        }
+
+        let mut results = result?;
+        results.page = query.page.unwrap_or(1);
+
+        Ok(results)
+    }
+
+    /// Drop `results` whose title isn't similar enough to the search,
+    /// if `min_title_similarity` is set.
+    fn retain_similar(&self, results: &mut Vec<SearchResultsMovie>) {
+        if let Some(min) = self.min_title_similarity {
+            retain_similar(results, &self.search, f32::from_bits(min));
+        }
+    }
+
+    /// Perform the same request as `get`, ordering the results from
+    /// newest to oldest.
+    ///
+    /// Results are ordered by the year they started, with results whose
+    /// year is unknown last.
+    pub async fn get_sorted_by_year_desc(&self) -> Result<SearchResults, Error> {
+        let mut results = self.get().await?;
+        sort_by_year_desc(&mut results.results);
+
+        Ok(results)
+    }
+
+    /// Perform OMDb Api request and return only the total number
+    /// of results this `SearchQuery` matches.
+    ///
+    /// This always requests the first page, regardless of `page`.
+    /// OMDb's `Movie not found!` is a count of `0`.
+    pub async fn count(&self) -> Result<usize, Error> {
+        let count = match self.year_queries()? {
+            Some(queries) => merge_years(queries, |query| async move {
+                Ok(SearchResults {
+                    results: Vec::new(),
+                    total_results: query.count_year().await?,
+                    page: 1,
+                })
+            })
+            .await
+            .map(|merged| merged.total_results),
+            None => self.count_year().await,
+        };
+
+        match count {
+            Err(Error::Api(ApiError::NotFound)) => Ok(0),
+            count => count,
+        }
+    }
+
+    /// Perform the request for `count`, for at most a single year.
+    async fn count_year(&self) -> Result<usize, Error> {
+        let query = self.checked()?;
+
+        let mut params = query.params();
+        params.retain(|(key, _)| *key != "page");
+
+        Ok(search_request(&query.http, params, &query.headers)
+            .await?
+            .total_results)
+    }
+
+    /// Fetch every page of results, concatenated in order.
+    ///
+    /// Paging stops early if OMDb runs out of results before the total
+    /// it reported. An error fetching any page is returned. Without
+    /// `with_client`, every page is fetched with one new
+    /// `reqwest::Client`, and so its connections.
+    pub async fn get_all(&self) -> Result<Vec<SearchResultsMovie>, Error> {
+        let query = self.sharing_client();
+        let mut results = match query.year_queries()? {
+            Some(queries) => {
+                merge_years(queries, |query| async move {
+                    let results = query.get_all_year().await?;
+                    Ok(SearchResults {
+                        total_results: results.len(),
+                        page: 1,
+                        results,
+                    })
+                })
+                .await?
+                .results
+            }
+            None => query.get_all_year().await?,
+        };
+
+        self.retain_similar(&mut results);
+
+        Ok(results)
+    }
+
+    /// Perform the requests for `get_all`, for at most a single year.
+    async fn get_all_year(&self) -> Result<Vec<SearchResultsMovie>, Error> {
+        let mut query = self.clone();
+
+        collect_pages(|page| {
+            query.page(page);
+            let query = query.clone();
+            async move { query.get_page().await }
+        })
+        .await
+    }
+
+    /// Fetch every page of results, skipping pages that fail.
+    ///
+    /// Returns the results of every page that succeeded, in order,
+    /// along with the numbers of the pages that failed. The first page
+    /// is needed to know how many pages there are, so an error fetching
+    /// it is returned as-is.
+    ///
+    /// Page numbers are only meaningful for a single year, so a
+    /// `year_range` isn't supported, use `get_all` or `paginate`.
+    pub async fn get_all_lenient(&self) -> Result<(Vec<SearchResultsMovie>, Vec<usize>), Error> {
+        if self.year_range.is_some() {
+            return Err(Error::Other("get_all_lenient doesn't support year_range"));
+        }
+
+        let mut query = self.sharing_client();
+
+        let (mut results, failed) = collect_pages_lenient(|page| {
+            query.page(page);
+            let query = query.clone();
+            async move { query.get_page().await }
+        })
+        .await?;
+        self.retain_similar(&mut results);
+
+        Ok((results, failed))
+    }
+
+    /// Fetch `count` results starting from the 1-based result `start`.
+    ///
+    /// Only the pages covering the range are requested, and the range
+    /// is clamped to the total number of results, so fewer than `count`
+    /// results may be returned. With `min_title_similarity`, results
+    /// are dropped from the range after it's fetched.
+    ///
+    /// Offsets are only meaningful for a single year, so a `year_range`
+    /// isn't supported, use `get_all` or `paginate`.
+    pub async fn range(
+        &self,
+        start: usize,
+        count: usize,
+    ) -> Result<Vec<SearchResultsMovie>, Error> {
+        if self.year_range.is_some() {
+            return Err(Error::Other("range doesn't support year_range"));
+        }
+
+        let mut query = self.sharing_client();
+
+        let mut results = collect_range(start, count, |page| {
+            query.page(page);
+            let query = query.clone();
+            async move { query.get_page().await }
+        })
+        .await?;
+        self.retain_similar(&mut results);
+
+        Ok(results)
+    }
+
+    /// A copy of this query with a checked API key, defaulting to
+    /// `OMDB_APIKEY`.
+    fn checked(&self) -> Result<SearchQuery, Error> {
+        self.checked_with(env::var("OMDB_APIKEY").ok())
+    }
+
+    /// Like `checked`, with `env_apikey` in place of `OMDB_APIKEY`.
+    fn checked_with(&self, env_apikey: Option<String>) -> Result<SearchQuery, Error> {
+        let mut query = self.clone();
+        query.apikey = default_apikey(self.apikey.as_deref(), env_apikey);
+        check_apikey(query.apikey.as_deref(), query.strict_apikey)?;
+
+        Ok(query)
+    }
+
+    /// A copy of this query whose requests share one client, for
+    /// sending several.
+    fn sharing_client(&self) -> SearchQuery {
+        let mut query = self.clone();
+        query.http.share_client();
+        query
+    }
+
+    /// A copy of this query for each year of its `year_range`, if it
+    /// has one.
+    fn year_queries(&self) -> Result<Option<Vec<SearchQuery>>, Error> {
+        let (start, end) = match self.year_range {
+            Some(range) => range,
+            None => return Ok(None),
+        };
+
+        if start > end {
+            return Err(Error::Other("year range starts after it ends"));
+        }
+
+        let queries = (start..=end)
+            .map(|year| {
+                let mut query = self.clone();
+                query.year(year);
+                query
+            })
+            .collect();
+
+        Ok(Some(queries))
+    }
+
+    /// Start a `SearchPager` over the pages of this `SearchQuery`.
+    ///
+    /// Paging starts at the page set with `page`, or the first page.
+    /// With a `year_range`, each year is paged through in turn. Like
+    /// `get`, years OMDb found nothing for are skipped, and results
+    /// already returned for an earlier year are dropped.
+    pub fn paginate(&self) -> SearchPager {
+        SearchPager {
+            query: self.sharing_client(),
+            years: None,
+            year: 0,
+            page: self.page.unwrap_or(1),
+            found: false,
+            seen: HashSet::new(),
+            done: false,
         }
+    }
+
+    /// Stream every result of this `SearchQuery`, fetching pages as
+    /// the stream is polled.
+    ///
+    /// Like `paginate`, the stream starts at the page set with `page`.
+    /// An error fetching a page is yielded as an item, after which the
+    /// stream ends. The stream isn't `Unpin`, so pin it, e.g. with
+    /// `Box::pin`, before polling it with `StreamExt::next`.
+    pub fn into_stream(self) -> impl Stream<Item = Result<SearchResultsMovie, Error>> {
+        let pages = stream::unfold(self.paginate(), |mut pager| async move {
+            let page = pager.next().await?;
+            Some((page, pager))
+        });
+
+        flatten_pages(pages)
+    }
+}
+
+/// The default number of lookups a `BatchQuery` has in flight at once.
+const BATCH_CONCURRENCY: usize = 4;
+
+/// A lookup of several movies by IMDb ID, sent concurrently.
+///
+/// Created with `omdb::imdb_ids`. Every lookup shares the same options,
+/// such as the API key and plot length.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BatchQuery {
+    ids: Vec<String>,
+    query: FindQuery,
+    concurrency: usize,
+}
+
+impl BatchQuery {
+    /// Apply builder methods to an owned query, returning it.
+    ///
+    /// See `FindQuery::with`.
+    pub fn with<F>(mut self, build: F) -> BatchQuery
+    where
+        F: FnOnce(&mut BatchQuery) -> &mut BatchQuery,
+    {
+        build(&mut self);
+        self
+    }
+
+    pub fn apikey<S: ToString>(&mut self, apikey: S) -> &mut BatchQuery {
+        self.query.apikey(apikey);
+        self
+    }
+
+    /// Send `user_agent` as the `User-Agent` header.
+    ///
+    /// See `FindQuery::user_agent`.
+    pub fn user_agent<S: Into<String>>(&mut self, user_agent: S) -> &mut BatchQuery {
+        self.query.user_agent(user_agent);
+        self
+    }
+
+    /// Specify the plot length.
+    pub fn plot(&mut self, plot: Plot) -> &mut BatchQuery {
+        self.query.plot(plot);
+        self
+    }
+
+    /// Send every lookup with `client` instead of a new `reqwest::Client`.
+    ///
+    /// See `FindQuery::with_client`.
+    pub fn with_client(&mut self, client: reqwest::Client) -> &mut BatchQuery {
+        self.query.with_client(client);
+        self
+    }
+
+    /// Send requests through `transport` instead of `reqwest`.
+    ///
+    /// See `Transport`.
+    pub fn transport(&mut self, transport: Arc<dyn Transport>) -> &mut BatchQuery {
+        self.query.transport(transport);
+        self
+    }
+
+    /// How every lookup's requests are sent, see `FindQuery::http_mut`.
+    pub(crate) fn http_mut(&mut self) -> &mut Http {
+        self.query.http_mut()
+    }
+
+    /// Send requests to `base_url` instead of OMDb.
+    ///
+    /// See `FindQuery::base_url`.
+    pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut BatchQuery {
+        self.query.base_url(base_url);
+        self
+    }
+
+    /// Request version `api_version` of OMDb's API.
+    ///
+    /// See `FindQuery::api_version`.
+    pub fn api_version<S: Into<String>>(&mut self, api_version: S) -> &mut BatchQuery {
+        self.query.api_version(api_version);
+        self
+    }
+
+    /// Give up on each request that takes longer than `timeout`.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut BatchQuery {
+        self.query.timeout(timeout);
+        self
+    }
+
+    /// Retry each request up to `retries` times.
+    ///
+    /// See `FindQuery::retries`.
+    pub fn retries(&mut self, retries: u32) -> &mut BatchQuery {
+        self.query.retries(retries);
+        self
+    }
+
+    /// Have at most `concurrency` lookups in flight at once.
+    /// Defaults to 4, and a `concurrency` of 0 is treated as 1.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut BatchQuery {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Hold a permit from `semaphore` for each lookup in flight.
+    ///
+    /// Sharing a semaphore between batches caps their lookups in flight
+    /// altogether, on top of each batch's `concurrency`. Lookups fail
+    /// with `Error::Other` if the semaphore is closed.
+    pub fn semaphore(&mut self, semaphore: Arc<Semaphore>) -> &mut BatchQuery {
+        self.query.http.semaphore = Some(semaphore);
+        self
+    }
+
+    /// Look up every movie, returning the results in the order of the IDs.
+    ///
+    /// Each lookup has its own result, so one that fails doesn't stop
+    /// the others. Without `with_client`, the lookups share one new
+    /// `reqwest::Client`, and so its connections.
+    pub async fn get(&self) -> Vec<Result<Movie, Error>> {
+        let semaphore = self.query.http.semaphore.clone();
+
+        fetch_batch(
+            self.lookups(),
+            self.concurrency,
+            semaphore,
+            |query| async move { query.get().await },
+        )
+        .await
+    }
+
+    /// A `FindQuery` for each of the IDs, sharing one client.
+    fn lookups(&self) -> Vec<FindQuery> {
+        let mut base = self.query.clone();
+        base.http.share_client();
+
+        self.ids
+            .iter()
+            .map(|id| {
+                let mut query = base.clone();
+                query.imdb_id = Some(id.clone());
+                query
+            })
+            .collect()
+    }
+}
+
+/// How similar two titles are, from `0.0` to `1.0`.
+///
+/// This is the Sørensen–Dice coefficient of the titles' lowercased
+/// character pairs.
+fn title_similarity(a: &str, b: &str) -> f32 {
+    fn pairs(s: &str) -> Vec<(char, char)> {
+        let chars: Vec<char> = s.to_lowercase().chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
+    if a.to_lowercase() == b.to_lowercase() {
+        return 1.0;
+    }
+
+    let a = pairs(a);
+    let mut b = pairs(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = a.len() + b.len();
+    let mut shared = 0;
+    for pair in a {
+        if let Some(i) = b.iter().position(|p| *p == pair) {
+            b.swap_remove(i);
+            shared += 1;
+        }
+    }
+
+    (2 * shared) as f32 / total as f32
+}
+
+impl fmt::Display for FindQuery {
+    /// Describes the query, like `find(imdb_id=tt0032138, year=1939)`.
+    ///
+    /// The API key is never included.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        describe(f, "find", self.params())
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    /// Describes the query, like `search(search=batman, page=2)`.
+    ///
+    /// The API key is never included.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        describe(f, "search", self.params())
+    }
+}
+
+/// Writes a description of a query's parameters, leaving out the API key.
+fn describe(f: &mut fmt::Formatter, name: &str, params: Vec<(&str, String)>) -> fmt::Result {
+    let params: Vec<String> = params
+        .into_iter()
+        .filter(|(key, _)| *key != "apikey")
+        .map(|(key, value)| {
+            let key = match key {
+                "i" => "imdb_id",
+                "t" => "title",
+                "s" => "search",
+                "y" => "year",
+                "type" => "kind",
+                key => key,
+            };
+            format!("{}={}", key, value)
+        })
+        .collect();
+
+    write!(f, "{}({})", name, params.join(", "))
+}
+
+/// Fetches `query` with `fetch`, retrying once with a short plot if
+/// a full plot was asked for with `plot_fallback` but none came back.
+async fn fetch_with_plot_fallback<F, Fut>(query: &FindQuery, mut fetch: F) -> Result<Movie, Error>
+where
+    F: FnMut(FindQuery) -> Fut,
+    Fut: Future<Output = Result<Movie, Error>>,
+{
+    let movie = fetch(query.clone()).await?;
+
+    if query.plot_fallback
+        && query.resolved_plot() == Some(Plot::Full)
+        && !crate::is_known(&movie.plot)
+    {
+        let mut short = query.clone();
+        short.plot = Some(Plot::Short);
+        // The full movie is still worth having without a plot
+        return Ok(fetch(short).await.unwrap_or(movie));
+    }
+
+    Ok(movie)
+}
+
+/// Fetches the series `query` with `fetch`, then each of its seasons,
+/// returning all of their episodes.
+async fn collect_episodes<F, Fut>(query: &FindQuery, mut fetch: F) -> Result<Vec<Episode>, Error>
+where
+    F: FnMut(FindQuery) -> Fut,
+    Fut: Future<Output = Result<Movie, Error>>,
+{
+    let mut series = query.clone();
+    series.season = None;
+    series.episode = None;
+
+    let total_seasons: u16 = fetch(series.clone())
+        .await?
+        .total_seasons
+        .parse()
+        .map_err(|_| Error::Other("series has no seasons"))?;
+
+    let mut episodes = Vec::new();
+    for season in 1..=total_seasons {
+        let mut query = series.clone();
+        query.season(season);
+        episodes.extend(fetch(query).await?.episodes);
+    }
+
+    Ok(episodes)
+}
+
+/// Fetches each of `queries` with `fetch`, at most `concurrency` at a
+/// time, keeping the results in order.
+async fn fetch_batch<I, F, Fut>(
+    queries: I,
+    concurrency: usize,
+    semaphore: Option<Arc<Semaphore>>,
+    mut fetch: F,
+) -> Vec<Result<Movie, Error>>
+where
+    I: IntoIterator<Item = FindQuery>,
+    F: FnMut(FindQuery) -> Fut,
+    Fut: Future<Output = Result<Movie, Error>>,
+{
+    stream::iter(queries)
+        .map(|query| {
+            let semaphore = semaphore.clone();
+            let lookup = fetch(query);
+
+            async move {
+                let _permit = match semaphore {
+                    Some(semaphore) => Some(
+                        semaphore
+                            .acquire_owned()
+                            .await
+                            .map_err(|_| Error::Other("batch semaphore was closed"))?,
+                    ),
+                    None => None,
+                };
+
+                lookup.await
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Fetches each of the single year `queries` with `fetch`, merging
+/// their results without duplicate IMDb IDs.
+///
+/// Years OMDb found nothing for are skipped, but if none found
+/// anything, that's returned as `ApiError::NotFound`.
+async fn merge_years<F, Fut>(
+    queries: Vec<SearchQuery>,
+    mut fetch: F,
+) -> Result<SearchResults, Error>
+where
+    F: FnMut(SearchQuery) -> Fut,
+    Fut: Future<Output = Result<SearchResults, Error>>,
+{
+    let mut merged = SearchResults {
+        results: Vec::new(),
+        total_results: 0,
+        page: 1,
+    };
+    let mut seen = HashSet::new();
+
+    for query in queries {
+        let results = match fetch(query).await {
+            Ok(results) => results,
+            Err(Error::Api(ApiError::NotFound)) => continue,
+            Err(err) => return Err(err),
+        };
+
+        merged.total_results += results.total_results;
+        for movie in results.results {
+            if seen.insert(movie.imdb_id.clone()) {
+                merged.results.push(movie);
+            }
+        }
+    }
+
+    if merged.total_results == 0 {
+        return Err(Error::Api(ApiError::NotFound));
+    }
+
+    Ok(merged)
+}
+
+/// Fetches every page with `fetch`.
+async fn collect_pages<F, Fut>(mut fetch: F) -> Result<Vec<SearchResultsMovie>, Error>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<SearchResults, Error>>,
+{
+    let first = fetch(1).await?;
+    let pages = first.total_results.div_ceil(PAGE_SIZE);
+
+    let mut results = first.results;
+    for page in 2..=pages {
+        // OMDb sometimes stops returning results before the total,
+        // either with an empty page or with "Movie not found!"
+        let next = match fetch(page).await {
+            Err(Error::Api(ApiError::NotFound)) => break,
+            next => next?,
+        };
+        if next.results.is_empty() {
+            break;
+        }
+        results.extend(next.results);
+    }
+
+    Ok(results)
+}
+
+/// Fetches every page with `fetch`, skipping pages that fail.
+async fn collect_pages_lenient<F, Fut>(
+    mut fetch: F,
+) -> Result<(Vec<SearchResultsMovie>, Vec<usize>), Error>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<SearchResults, Error>>,
+{
+    let first = fetch(1).await?;
+    let pages = first.total_results.div_ceil(PAGE_SIZE);
+
+    let mut results = first.results;
+    let mut failed = Vec::new();
+    for page in 2..=pages {
+        match fetch(page).await {
+            // OMDb sometimes stops returning results before the total
+            Ok(ref next) if next.results.is_empty() => break,
+            Err(Error::Api(ApiError::NotFound)) => break,
+            Ok(next) => results.extend(next.results),
+            Err(_) => failed.push(page),
+        }
+    }
+
+    Ok((results, failed))
+}
+
+/// Fetches the pages covering results `start..start + count` with
+/// `fetch`, returning just those results.
+async fn collect_range<F, Fut>(
+    start: usize,
+    count: usize,
+    mut fetch: F,
+) -> Result<Vec<SearchResultsMovie>, Error>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<SearchResults, Error>>,
+{
+    if start == 0 {
+        return Err(Error::Other("start must be >= 1"));
+    }
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Zero-based offsets of the range, and the page holding its start
+    let offset = start - 1;
+    let first_page = offset / PAGE_SIZE + 1;
+
+    let first = fetch(first_page).await?;
+    let end = (offset + count).min(first.total_results);
+    if offset >= end {
+        return Ok(Vec::new());
+    }
+    let last_page = (end - 1) / PAGE_SIZE + 1;
+
+    let mut results = first.results;
+    for page in first_page + 1..=last_page {
+        results.extend(fetch(page).await?.results);
+    }
+
+    Ok(results
+        .into_iter()
+        .skip(offset - (first_page - 1) * PAGE_SIZE)
+        .take(end - offset)
+        .collect())
+}
+
+/// Whether a search came back without any results.
+fn is_empty_search(result: &Result<SearchResults, Error>) -> bool {
+    match result {
+        Ok(results) => results.results.is_empty(),
+        Err(Error::Api(ApiError::NotFound)) => true,
+        _ => false,
+    }
+}
+
+/// Tidies up a search term, collapsing letters repeated in a row and
+/// trimming punctuation from the end.
+fn normalize_search(search: &str) -> String {
+    let mut normalized = String::with_capacity(search.len());
+    for c in search.chars() {
+        let repeated = c.is_alphabetic()
+            && normalized
+                .chars()
+                .last()
+                .is_some_and(|last: char| last.to_lowercase().eq(c.to_lowercase()));
+
+        if !repeated {
+            normalized.push(c);
+        }
+    }
+
+    normalized
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+        .to_owned()
+}
+
+/// Sorts search results newest first, with unknown years last.
+fn sort_by_year_desc(results: &mut [SearchResultsMovie]) {
+    results.sort_by_key(|movie| Reverse(movie.year_range().map(|(start, _)| start)));
+}
+
+/// Removes results whose title isn't similar enough to `search`.
+fn retain_similar(results: &mut Vec<SearchResultsMovie>, search: &str, min: f32) {
+    results.retain(|movie| title_similarity(search, &movie.title) >= min);
+}
+
+/// Fetches the pages of a `SearchQuery` one after another.
+///
+/// Created with `SearchQuery::paginate`.
+#[derive(Debug)]
+pub struct SearchPager {
+    query: SearchQuery,
+    // A query for each year of a `year_range`, or just `query`,
+    // filled in by the first call to `next`
+    years: Option<Vec<SearchQuery>>,
+    year: usize,
+    page: usize,
+    // Whether any year has found results, and the ones it found
+    found: bool,
+    seen: HashSet<String>,
+    done: bool,
+}
+
+impl SearchPager {
+    /// Fetch the next page of results.
+    ///
+    /// Returns `None` once every page of `total_results` has been fetched,
+    /// OMDb runs out of results early, or after an error has been
+    /// returned. With a `year_range`, each
+    /// page is from a single year, and its `total_results` that year's.
+    pub async fn next(&mut self) -> Option<Result<SearchResults, Error>> {
+        let ranged = self.query.year_range.is_some();
+
+        while !self.done {
+            let mut query = match self.current() {
+                Ok(query) => query,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            };
+
+            match query.page(self.page).get_page().await {
+                Ok(mut results) => {
+                    // Paging follows OMDb's results, before any are dropped
+                    if results.results.is_empty() || self.page * PAGE_SIZE >= results.total_results
+                    {
+                        self.next_year();
+                    } else {
+                        self.page += 1;
+                    }
+                    self.found = true;
+
+                    if ranged {
+                        let seen = &mut self.seen;
+                        results
+                            .results
+                            .retain(|movie| seen.insert(movie.imdb_id.clone()));
+                    }
+                    self.query.retain_similar(&mut results.results);
+
+                    return Some(Ok(results));
+                }
+                // Past the first page, OMDb has run out of results early
+                Err(Error::Api(ApiError::NotFound))
+                    if ranged || self.page > self.query.page.unwrap_or(1) =>
+                {
+                    self.next_year();
+                    if self.done && !self.found {
+                        return Some(Err(Error::Api(ApiError::NotFound)));
+                    }
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The query for the year being paged through.
+    fn current(&mut self) -> Result<SearchQuery, Error> {
+        if self.years.is_none() {
+            let years = self.query.year_queries()?;
+            self.years = Some(years.unwrap_or_else(|| vec![self.query.clone()]));
+        }
+
+        let years = self.years.as_ref().expect("years were just filled in");
+        Ok(years[self.year].clone())
+    }
+
+    /// Move on to the first page of the next year, if there is one.
+    fn next_year(&mut self) {
+        self.year += 1;
+        self.page = self.query.page.unwrap_or(1);
+        self.done = self.year >= self.years.as_ref().map_or(0, Vec::len);
+    }
+}
+
+/// Flattens a stream of pages into a stream of their results.
+fn flatten_pages<S>(pages: S) -> impl Stream<Item = Result<SearchResultsMovie, Error>>
+where
+    S: Stream<Item = Result<SearchResults, Error>>,
+{
+    pages.flat_map(|page| {
+        let items: Vec<_> = match page {
+            Ok(page) => page.results.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        };
+
+        stream::iter(items)
+    })
+}
+
+/// Sends a search request and turns OMDb's response into `SearchResults`.
+async fn search_request(
+    http: &Http,
+    params: Vec<(&str, String)>,
+    headers: &[(String, String)],
+) -> Result<SearchResults, Error> {
+    if let Some(Cached::Search(results)) = http.cached(&params) {
+        return Ok(results);
+    }
+
+    // Send our request
+    let response: SearchResponse = send(http, params.clone(), headers).await?;
+
+    // Check if the Api's Response string equals true
+    if response.response.to_lowercase() != "true" {
+        // Return with the Api's Error field or "undefined" if empty
+        return Err(api_error(response.error.as_deref().unwrap_or("undefined")));
+    }
+
+    let results: SearchResults = response.into();
+    http.store(&params, Cached::Search(results.clone()));
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIZARD_OF_OZ: &str = r#"{
+        "Title": "The Wizard of Oz",
+        "Year": "1939",
+        "Rated": "G",
+        "Released": "25 Aug 1939",
+        "Runtime": "102 min",
+        "Genre": "Adventure, Family, Fantasy",
+        "Director": "Victor Fleming, George Cukor, Mervyn LeRoy",
+        "Writer": "Noel Langley, Florence Ryerson, Edgar Allan Woolf",
+        "Actors": "Judy Garland, Frank Morgan, Ray Bolger",
+        "Plot": "Young Dorothy Gale and her dog Toto are swept away by a tornado from their Kansas farm to the magical Land of Oz.",
+        "Language": "English",
+        "Country": "United States",
+        "Awards": "Won 2 Oscars. 13 wins & 16 nominations total",
+        "Poster": "https://m.media-amazon.com/images/M/MV5BNjUyMTc4MDExMV5BMl5BanBnXkFtZTgwNDg0NDIwMjE@._V1_SX300.jpg",
+        "Ratings": [
+            {"Source": "Internet Movie Database", "Value": "8.1/10"},
+            {"Source": "Rotten Tomatoes", "Value": "98%"},
+            {"Source": "Metacritic", "Value": "92/100"}
+        ],
+        "Metascore": "92",
+        "imdbRating": "8.1",
+        "imdbVotes": "408,915",
+        "imdbID": "tt0032138",
+        "Type": "movie",
+        "DVD": "06 Dec 2005",
+        "BoxOffice": "$24,668,669",
+        "Production": "N/A",
+        "Website": "N/A",
+        "Response": "True"
+    }"#;
+
+    #[test]
+    fn find_query_string_round_trip() {
+        let query = FindQuery::from_query_string("t=batman&y=1989&type=movie&foo=bar").unwrap();
+
+        assert_eq!(
+            query.params(),
+            vec![
+                ("t", String::from("batman")),
+                ("type", String::from("movie")),
+                ("y", String::from("1989")),
+                ("foo", String::from("bar")),
+            ]
+        );
+    }
+
+    #[test]
+    fn api_version() {
+        let mut query = title("batman");
+        assert!(query.plan().unwrap().url.contains("?v=1&r=json&"));
+
+        query.api_version("2");
+        assert!(query.plan().unwrap().url.contains("?v=2&r=json&"));
+        assert_ne!(query, title("batman"));
+
+        let parsed = FindQuery::from_query_string("t=batman&v=2&r=json").unwrap();
+        assert_eq!(parsed, query);
+    }
+
+    #[test]
+    fn query_string_from_url() {
+        let round_trip = |query: &mut FindQuery| {
+            let url = query.plan().unwrap().url;
+            let (_, query_string) = url.split_once('?').unwrap();
+            FindQuery::from_query_string(query_string).unwrap()
+        };
+
+        let mut query = title("The Wizard of Oz");
+        query.year(1939).kind(Kind::Movie);
+        assert_eq!(round_trip(&mut query), query);
+
+        query.api_version("2");
+        assert_eq!(round_trip(&mut query), query);
+    }
+
+    #[test]
+    fn conditional_builder() {
+        let build = |year: Option<u16>| {
+            let mut query = search("batman").with(|q| q.apikey("1a2b3c4d").kind(Kind::Movie));
+            if let Some(year) = year {
+                query = query.with(|q| q.year(year));
+            }
+            query
+        };
+
+        let mut expected = search("batman");
+        expected.apikey("1a2b3c4d").kind(Kind::Movie);
+        assert_eq!(build(None), expected);
+
+        expected.year(1989);
+        assert_eq!(build(Some(1989)), expected);
+    }
+
+    #[test]
+    fn default_plot() {
+        let mut query = imdb_id("tt0032138");
+        query.plot(Plot::Full).plot(Plot::Default);
+        assert_eq!(query, imdb_id("tt0032138"));
+    }
+
+    #[test]
+    fn find_result_missing_title() {
+        for json in &[
+            r#"{"Response":"True","imdbID":"tt0032138"}"#,
+            r#"{"Response":"True","Title":"","imdbID":"tt0032138"}"#,
+            r#"{"Response":"True","Title":"N/A","imdbID":"tt0032138"}"#,
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":""}"#,
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"N/A"}"#,
+        ] {
+            match find_result(serde_json::from_str(json).unwrap()) {
+                Err(Error::Other(desc)) => assert_eq!(desc, "malformed successful response"),
+                other => panic!("unexpected result for {}: {:?}", json, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn strict_apikey() {
+        match title("The Wizard of Oz")
+            .apikey("")
+            .strict_apikey(true)
+            .get()
+            .await
+        {
+            Err(Error::Other(desc)) => assert_eq!(desc, "malformed API key"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert!(check_apikey(Some("1a2b3C4d"), true).is_ok());
+        assert!(check_apikey(Some("1a2b3c4"), true).is_err());
+        assert!(check_apikey(Some("1a2b3c4g"), true).is_err());
+        assert!(check_apikey(Some("1a2b3c4g"), false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn invalid_imdb_id() {
+        assert!(check_imdb_id("tt0032138").is_ok());
+        assert!(check_imdb_id("tt10872600").is_ok());
+        assert!(check_imdb_id("tt003213").is_err());
+        assert!(check_imdb_id("12345").is_err());
+
+        match imdb_id("foo").apikey("1a2b3c4d").get().await {
+            Err(Error::InvalidImdbId(id)) => assert_eq!(id, "foo"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn redacted_url() {
+        let url = reqwest::Url::parse("https://omdbapi.com/?i=tt0032138&apikey=1a2b3c4d").unwrap();
+        assert_eq!(
+            redact(&url),
+            "https://omdbapi.com/?i=tt0032138&apikey=REDACTED"
+        );
+    }
+
+    #[tokio::test]
+    async fn env_apikey() {
+        let body = r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#;
+        let (url, server) = crate::mock::serve(vec![body, body]);
+        let env_apikey = || Some(String::from("5e6f7a8b"));
+
+        // Without a key, the one from the environment is sent
+        let query = imdb_id("tt0032138")
+            .base_url(&url)
+            .checked_with(env_apikey())
+            .unwrap();
+        query.get().await.unwrap();
+
+        // An explicit key takes precedence
+        let query = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .checked_with(env_apikey())
+            .unwrap();
+        query.get().await.unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("&apikey=5e6f7a8b "));
+        assert!(requests[1].contains("&apikey=1a2b3c4d "));
+
+        let search = search("batman").checked_with(env_apikey()).unwrap();
+        assert!(search
+            .params()
+            .contains(&("apikey", String::from("5e6f7a8b"))));
+    }
+
+    #[test]
+    fn missing_apikey() {
+        match title("The Wizard of Oz").checked_with(None) {
+            Err(Error::MissingApiKey) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match search("batman").checked_with(None) {
+            Err(Error::MissingApiKey) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn find_result_infers_kind() {
+        let kind = |json: &str| {
+            find_result(serde_json::from_str(json).unwrap())
+                .unwrap()
+                .kind
+        };
+
+        assert_eq!(
+            kind(
+                r#"{"Response":"True","Title":"Silicon Valley","imdbID":"tt2575988","totalSeasons":"6"}"#
+            ),
+            Kind::Series
+        );
+        assert_eq!(
+            kind(
+                r#"{"Response":"True","Title":"Minimum Viable Product","imdbID":"tt3222784","Season":"1","Episode":"1"}"#
+            ),
+            Kind::Episode
+        );
+        assert_eq!(
+            kind(
+                r#"{"Response":"True","Title":"Silicon Valley","imdbID":"tt2575988","Type":"movie","totalSeasons":"6"}"#
+            ),
+            Kind::Movie
+        );
+        assert_eq!(
+            kind(r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#),
+            Kind::Movie
+        );
+    }
+
+    fn hit(title: &str, year: &str) -> SearchResultsMovie {
+        SearchResultsMovie {
+            title: String::from(title),
+            year: String::from(year),
+            imdb_id: String::new(),
+            poster: String::new(),
+            kind: Kind::Movie,
+        }
+    }
+
+    #[test]
+    fn sorted_by_year_desc() {
+        let mut results = vec![
+            hit("Batman", "1989"),
+            hit("Batman Beyond", "N/A"),
+            hit("The Batman", "2022"),
+            hit("Batman: The Animated Series", "1992–1995"),
+        ];
+        sort_by_year_desc(&mut results);
+
+        let years: Vec<_> = results.iter().map(|m| m.year.as_str()).collect();
+        assert_eq!(years, vec!["2022", "1992–1995", "1989", "N/A"]);
+    }
+
+    #[tokio::test]
+    async fn plot_fallback() {
+        let fetch = |query: FindQuery| async move {
+            let mut movie = find_result(serde_json::from_str(WIZARD_OF_OZ).unwrap())?;
+            movie.plot = match query.plot {
+                Some(Plot::Full) => String::from("N/A"),
+                _ => String::from("Dorothy Gale is swept away to a magical land."),
+            };
+            Ok(movie)
+        };
+
+        let mut query = imdb_id("tt0032138");
+        query.plot(Plot::Full);
+        let movie = fetch_with_plot_fallback(&query, fetch).await.unwrap();
+        assert_eq!(movie.plot, "N/A");
+
+        query.plot_fallback(true);
+        let movie = fetch_with_plot_fallback(&query, fetch).await.unwrap();
+        assert_eq!(movie.plot, "Dorothy Gale is swept away to a magical land.");
+
+        let failing = |query: FindQuery| async move {
+            match query.plot {
+                Some(Plot::Short) => Err(Error::Status(reqwest::StatusCode::BAD_GATEWAY)),
+                _ => fetch(query).await,
+            }
+        };
+        let movie = fetch_with_plot_fallback(&query, failing).await.unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+        assert_eq!(movie.plot, "N/A");
+    }
+
+    #[tokio::test]
+    async fn result_range() {
+        // 28 results, so the last page is short
+        let fetch = |page: usize| async move {
+            let results = ((page - 1) * PAGE_SIZE + 1..=page * PAGE_SIZE)
+                .filter(|i| *i <= 28)
+                .map(|i| hit(&format!("Result {}", i), "2000"))
+                .collect();
+
+            Ok(SearchResults {
+                results,
+                total_results: 28,
+                page: 1,
+            })
+        };
+        let titles = |results: Vec<SearchResultsMovie>| -> Vec<String> {
+            results.into_iter().map(|m| m.title).collect()
+        };
+
+        let results = collect_range(15, 10, fetch).await.unwrap();
+        let expected: Vec<_> = (15..25).map(|i| format!("Result {}", i)).collect();
+        assert_eq!(titles(results), expected);
+
+        // Clamped to the total number of results
+        let results = collect_range(25, 10, fetch).await.unwrap();
+        assert_eq!(titles(results).len(), 4);
+        assert!(collect_range(40, 10, fetch).await.unwrap().is_empty());
+        assert!(collect_range(0, 10, fetch).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn all_pages() {
+        // Claims 4 pages, but runs dry after the second
+        let fetch = |page: usize| async move {
+            let title = format!("Page {}", page);
+            let results = match page {
+                1 | 2 => vec![hit(&title, "2000"), hit(&title, "2001")],
+                _ => Vec::new(),
+            };
+
+            Ok(SearchResults {
+                results,
+                total_results: 35,
+                page: 1,
+            })
+        };
+
+        let results = collect_pages(fetch).await.unwrap();
+        let titles: Vec<_> = results.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["Page 1", "Page 1", "Page 2", "Page 2"]);
+
+        let failing = collect_pages(|page| async move {
+            if page == 2 {
+                return Err(Error::api("Error getting data."));
+            }
+            fetch(page).await
+        });
+        assert!(failing.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stream_results() {
+        let page = |title: &str| {
+            Ok(SearchResults {
+                results: vec![hit(title, "2000"), hit(title, "2001")],
+                total_results: 25,
+                page: 1,
+            })
+        };
+        let pages = stream::iter(vec![
+            page("Page 1"),
+            page("Page 2"),
+            Err(Error::api("Error getting data.")),
+        ]);
+
+        let items: Vec<_> = flatten_pages(pages).collect().await;
+        assert_eq!(items.len(), 5);
+
+        let titles: Vec<_> = items[..4]
+            .iter()
+            .map(|item| item.as_ref().unwrap().title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Page 1", "Page 1", "Page 2", "Page 2"]);
+        assert!(items[4].is_err());
+    }
+
+    #[tokio::test]
+    async fn batch_keeps_order_and_errors() {
+        let queries = vec![
+            imdb_id("tt0032138"),
+            imdb_id("tt0000000"),
+            imdb_id("tt2575988"),
+        ];
+
+        let results = fetch_batch(queries, 2, None, |query| async move {
+            let id = query.imdb_id.unwrap();
+            if id == "tt0000000" {
+                return Err(Error::api("Incorrect IMDb ID."));
+            }
+
+            let mut movie = find_result(serde_json::from_str(WIZARD_OF_OZ).unwrap())?;
+            movie.imdb_id = id;
+            Ok(movie)
+        })
+        .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().imdb_id, "tt0032138");
+        assert!(matches!(
+            results[1],
+            Err(Error::Api(ApiError::IncorrectImdbId))
+        ));
+        assert_eq!(results[2].as_ref().unwrap().imdb_id, "tt2575988");
+    }
+
+    #[test]
+    fn pages_share_client() {
+        let query = search("batman");
+        assert!(query.http.client.is_none());
+        assert!(query.sharing_client().http.client.is_some());
+        assert!(query.paginate().query.http.client.is_some());
+    }
+
+    #[tokio::test]
+    async fn batch_semaphore() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let semaphore = Arc::new(Semaphore::new(2));
+        let in_flight = AtomicUsize::new(0);
+        let most = AtomicUsize::new(0);
+
+        let batch = || {
+            let queries = (0..4).map(|i| imdb_id(format!("tt{:07}", i)));
+            fetch_batch(queries, 4, Some(semaphore.clone()), |_| async {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                most.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                find_result(serde_json::from_str(WIZARD_OF_OZ).unwrap())
+            })
+        };
+
+        let (first, second) = tokio::join!(batch(), batch());
+        assert!(first.iter().chain(&second).all(Result::is_ok));
+        assert_eq!(most.load(Ordering::SeqCst), 2);
+
+        semaphore.close();
+        assert!(batch().await.iter().all(Result::is_err));
+    }
+
+    #[test]
+    fn batch_shares_client() {
+        let lookups = imdb_ids(vec!["tt0032138", "tt2575988"]).lookups();
+
+        let ids: Vec<_> = lookups.iter().map(|q| q.imdb_id.as_deref()).collect();
+        assert_eq!(ids, vec![Some("tt0032138"), Some("tt2575988")]);
+        assert!(lookups.iter().all(|q| q.http.client.is_some()));
+    }
+
+    #[tokio::test]
+    async fn year_range() {
+        let mut query = search("batman");
+        query.year(1999).year_range(2000, 2002);
+        assert_eq!(query.year, None);
+
+        let queries = query.year_queries().unwrap().unwrap();
+        let years: Vec<_> = queries.iter().map(|q| q.year.clone().unwrap()).collect();
+        assert_eq!(years, vec!["2000", "2001", "2002"]);
+
+        let merged = merge_years(queries, |query| async move {
+            let year = query.year.unwrap();
+            let mut same = hit("Batman Beyond", &year);
+            same.imdb_id = String::from("tt0147746");
+            let mut other = hit("Batman", &year);
+            other.imdb_id = format!("tt{}", year);
+
+            match year.as_str() {
+                "2001" => Err(Error::api("Movie not found!")),
+                _ => Ok(SearchResults {
+                    results: vec![same, other],
+                    total_results: 2,
+                    page: 1,
+                }),
+            }
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<_> = merged.results.iter().map(|m| m.imdb_id.as_str()).collect();
+        assert_eq!(ids, vec!["tt0147746", "tt2000", "tt2002"]);
+        assert_eq!(merged.total_results, 4);
+
+        query.year_range(2002, 2000);
+        assert!(query.year_queries().is_err());
+        query.year(1989);
+        assert!(query.year_queries().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn lenient_pages() {
+        let (results, failed) = collect_pages_lenient(|page| async move {
+            if page == 2 {
+                return Err(Error::api("Error getting data."));
+            }
+
+            let title = format!("Page {}", page);
+            Ok(SearchResults {
+                results: vec![hit(&title, "2000"), hit(&title, "2001")],
+                total_results: 25,
+                page: 1,
+            })
+        })
+        .await
+        .unwrap();
+
+        let titles: Vec<_> = results.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["Page 1", "Page 1", "Page 3", "Page 3"]);
+        assert_eq!(failed, vec![2]);
+    }
+
+    #[test]
+    fn min_title_similarity() {
+        let movie = |title: &str| hit(title, "1989");
+
+        let mut results = SearchResults {
+            results: vec![
+                movie("Batman"),
+                movie("Batman Returns"),
+                movie("Alyas Batman en Robin"),
+            ],
+            total_results: 3,
+            page: 1,
+        };
+        retain_similar(&mut results.results, "batman", 0.5);
+
+        let titles: Vec<_> = results.results.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["Batman", "Batman Returns"]);
+        assert_eq!(results.total_results, 3);
+    }
+
+    #[test]
+    fn base_url_from_env() {
+        let env_base_url = || Some(String::from("http://localhost:8080"));
+
+        assert_eq!(
+            endpoint(Some("http://127.0.0.1:3000"), env_base_url()),
+            "http://127.0.0.1:3000"
+        );
+        assert_eq!(endpoint(None, env_base_url()), "http://localhost:8080");
+        assert_eq!(endpoint(None, None), "https://omdbapi.com");
+    }
+
+    #[tokio::test]
+    async fn accept_encoding() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let movie = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get()
+            .await;
+        assert_eq!(movie.unwrap().title, "The Wizard of Oz");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /?v=1&r=json&i=tt0032138&apikey=1a2b3c4d "));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("accept-encoding: gzip, br"));
+    }
+
+    #[tokio::test]
+    async fn decode_invalid_apikey_page() {
+        let (url, server) = crate::mock::serve_as(
+            "text/html; charset=utf-8",
+            vec!["<html><body><h1>Invalid API key!</h1></body></html>"],
+        );
+
+        let response = reqwest::get(&url).await.unwrap();
+        match decode::<FindResponse>(response, None).await {
+            Err(Error::InvalidApiKey) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn decode_html_page() {
+        let (url, server) = crate::mock::serve_as(
+            "text/html",
+            vec!["<html><body><h1>502 Bad Gateway</h1></body></html>"],
+        );
+
+        let response = reqwest::get(&url).await.unwrap();
+        match decode::<FindResponse>(response, None).await {
+            Err(Error::Decode { ref body, .. }) => {
+                assert_eq!(body, "<html><body><h1>502 Bad Gateway</h1></body></html>")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn decode_byte_order_mark() {
+        let (url, server) = crate::mock::serve(vec![
+            "\u{feff}{\"Response\":\"True\",\"Title\":\"The Wizard of Oz\",\"imdbID\":\"tt0032138\"}",
+        ]);
+
+        let response = reqwest::get(&url).await.unwrap();
+        let movie = find_result(decode(response, None).await.unwrap()).unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn with_key() {
+        let mut query = title("The Wizard of Oz");
+        query.apikey("1a2b3c4d");
+
+        assert!(query
+            .with_key("5e6f7a8b")
+            .params()
+            .contains(&("apikey", String::from("5e6f7a8b"))));
+        assert!(query
+            .with_key("9c0d1e2f")
+            .params()
+            .contains(&("apikey", String::from("9c0d1e2f"))));
+        assert_eq!(query.apikey.as_deref(), Some("1a2b3c4d"));
+    }
+
+    #[test]
+    fn not_available_is_empty() {
+        let movie = find_result(
+            serde_json::from_str(
+                r#"{"Response":"True","Title":"Up","imdbID":"tt1049413","Poster":"N/A","Metascore":"N/A","Plot":"N/A","OriginalTitle":"N/A"}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(movie.poster, "");
+        assert_eq!(movie.plot, "");
+        assert_eq!(movie.metascore_u8(), None);
+        assert_eq!(movie.original_title, None);
+
+        let results: SearchResults = serde_json::from_str::<SearchResponse>(
+            r#"{"Response":"True","totalResults":"1","Search":[{"Title":"Up","Year":"2009","imdbID":"tt1049413","Type":"movie","Poster":"N/A"}]}"#,
+        )
+        .unwrap()
+        .into();
+        assert_eq!(results.results[0].poster, "");
+    }
+
+    #[test]
+    fn season_and_episode() {
+        let mut query = title("Game of Thrones");
+        query.kind(Kind::Series).season(1);
+        assert!(query.params().contains(&("Season", String::from("1"))));
+        assert!(query.check_episode().is_ok());
+
+        query.episode(3);
+        assert!(query.params().contains(&("Episode", String::from("3"))));
+        assert_eq!(
+            FindQuery::from_query_string("t=Game+of+Thrones&type=series&Season=1&Episode=3")
+                .unwrap(),
+            query
+        );
+
+        assert!(query.kind(Kind::Movie).check_episode().is_err());
+        assert!(title("Game of Thrones").episode(3).check_episode().is_err());
+
+        let movie = find_result(
+            serde_json::from_str(
+                r#"{"Title":"Game of Thrones","Season":"1","totalSeasons":"8","Episodes":[
+                    {"Title":"Winter Is Coming","Released":"2011-04-17","Episode":"1","imdbRating":"8.9","imdbID":"tt1480055"},
+                    {"Title":"The Kingsroad","Released":"2011-04-24","Episode":"2","imdbRating":"N/A","imdbID":"tt1668746"}
+                ],"Response":"True"}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(movie.kind, Kind::Series);
+        assert_eq!(movie.episodes.len(), 2);
+        assert_eq!(movie.episodes[0].title, "Winter Is Coming");
+        assert_eq!(movie.episodes[1].episode, "2");
+        assert_eq!(movie.episodes[1].imdb_rating, None);
+    }
+
+    #[tokio::test]
+    async fn all_episodes() {
+        let fetch = |query: FindQuery| async move {
+            let json = match query.season {
+                None => String::from(
+                    r#"{"Title":"Fleabag","imdbID":"tt5687612","Type":"series","totalSeasons":"2","Response":"True"}"#,
+                ),
+                Some(season) => format!(
+                    r#"{{"Title":"Fleabag","Season":"{0}","totalSeasons":"2","Episodes":[
+                        {{"Title":"S{0}E1","Episode":"1","imdbID":"tt{0}1"}},
+                        {{"Title":"S{0}E2","Episode":"2","imdbID":"tt{0}2"}}
+                    ],"Response":"True"}}"#,
+                    season
+                ),
+            };
+
+            find_result(serde_json::from_str(&json).unwrap())
+        };
+
+        let mut query = title("Fleabag");
+        query.season(2);
+        let episodes = collect_episodes(&query, fetch).await.unwrap();
+
+        let titles: Vec<_> = episodes.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["S1E1", "S1E2", "S2E1", "S2E2"]);
+        let seasons: Vec<_> = episodes.iter().map(|e| e.season).collect();
+        assert_eq!(seasons, vec![1, 1, 2, 2]);
+
+        // A failing season fails the whole fetch
+        let failing = collect_episodes(&query, |query| async move {
+            if query.season == Some(2) {
+                return Err(Error::api("Series or Episode not found!"));
+            }
+            fetch(query).await
+        });
+        assert!(failing.await.is_err());
+    }
+
+    #[test]
+    fn ratings() {
+        let movie = find_result(serde_json::from_str(WIZARD_OF_OZ).unwrap()).unwrap();
+        assert_eq!(movie.ratings.len(), 3);
+        assert_eq!(movie.ratings[1].source, "Rotten Tomatoes");
+        assert_eq!(movie.rotten_tomatoes(), Some(98));
+        assert_eq!(movie.metacritic(), Some(92));
+
+        let movie = find_result(
+            serde_json::from_str(r#"{"Response":"True","Title":"Up","imdbID":"tt1049413"}"#)
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(movie.ratings.is_empty());
+    }
+
+    #[test]
+    fn omdb_json_round_trip() {
+        let movie = find_result(serde_json::from_str(WIZARD_OF_OZ).unwrap()).unwrap();
+        let json = movie.to_omdb_json();
+        assert_eq!(json["imdbID"], "tt0032138");
+        assert_eq!(json["Type"], "movie");
+
+        let again = find_result(serde_json::from_value(json).unwrap()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&movie).unwrap(),
+            serde_json::to_value(&again).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn page_zero() {
+        match search("batman").apikey("1a2b3c4d").page(0).get().await {
+            Err(Error::Other(desc)) => assert_eq!(desc, "page must be >= 1"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn normalized_search() {
+        assert_eq!(normalize_search("Battmann!!"), "Batman");
+        assert_eq!(normalize_search("batman"), "batman");
+
+        assert!(is_empty_search(&Err(Error::api("Movie not found!"))));
+        assert!(!is_empty_search(&Err(Error::api("Invalid API key!"))));
+    }
+
+    #[test]
+    fn request_plan() {
+        let plan = title("The Wizard of Oz")
+            .apikey("1a2b3c4d")
+            .year(1939)
+            .kind(Kind::Movie)
+            .plot(Plot::Full)
+            .request_id("abc-123")
+            .base_url("http://localhost:8080")
+            .api_version("2")
+            .timeout(Duration::from_secs(5))
+            .retries(3)
+            .retry_backoff(Duration::from_millis(500))
+            .plan()
+            .unwrap();
+
+        assert_eq!(plan.base_url, "http://localhost:8080");
+        assert!(plan.url.starts_with(&plan.base_url));
+        assert_eq!(plan.api_version, "2");
+        assert_eq!(plan.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(plan.retries, 3);
+        assert_eq!(plan.retry_backoff, Duration::from_millis(500));
+        assert_eq!(
+            plan.params,
+            vec![
+                (String::from("t"), String::from("The Wizard of Oz")),
+                (String::from("apikey"), String::from("REDACTED")),
+                (String::from("type"), String::from("movie")),
+                (String::from("y"), String::from("1939")),
+                (String::from("plot"), String::from("full")),
+            ]
+        );
+        assert_eq!(
+            plan.headers,
+            vec![(String::from("X-Request-Id"), String::from("abc-123"))]
+        );
+        assert!(plan.url.ends_with(
+            "/?v=2&r=json&t=The+Wizard+of+Oz&apikey=REDACTED&type=movie&y=1939&plot=full"
+        ));
+        assert!(!plan.url.contains("1a2b3c4d"));
+    }
+
+    #[test]
+    fn original_title() {
+        let movie = find_result(serde_json::from_str(WIZARD_OF_OZ).unwrap()).unwrap();
+        assert_eq!(movie.original_title, None);
+
+        let movie = find_result(
+            serde_json::from_str(
+                r#"{"Response":"True","Title":"Spirited Away","OriginalTitle":"Sen to Chihiro no kamikakushi","imdbID":"tt0245429"}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            movie.original_title.as_deref(),
+            Some("Sen to Chihiro no kamikakushi")
+        );
+    }
+
+    #[test]
+    fn display() {
+        let find = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .year(1939)
+            .to_string();
+        assert_eq!(find, "find(imdb_id=tt0032138, year=1939)");
+
+        let search = search("batman").apikey("1a2b3c4d").page(2).to_string();
+        assert_eq!(search, "search(search=batman, page=2)");
+        assert!(!search.contains("1a2b3c4d"));
+    }
+
+    #[tokio::test]
+    async fn with_client() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Pool", "shared".parse().unwrap());
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let mut query = title("The Wizard of Oz");
+        query.with_client(client);
+
+        // Send the planned request to the mock server with the query's client
+        let mut request = build_request(&query.http, query.params(), &query.headers)
+            .build()
+            .unwrap();
+        *request.url_mut() = url.parse().unwrap();
+        let response: FindResponse =
+            decode(query.http.client().execute(request).await.unwrap(), None)
+                .await
+                .unwrap();
+        assert_eq!(find_result(response).unwrap().title, "The Wizard of Oz");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("x-pool: shared"));
+    }
+
+    #[tokio::test]
+    async fn retries() {
+        use reqwest::StatusCode;
+        use std::cell::Cell;
+
+        let attempts = Cell::new(0);
+        let result = with_retries(3, Duration::from_millis(1), || async {
+            attempts.set(attempts.get() + 1);
+            match attempts.get() {
+                1 | 2 => Err(Error::Status(StatusCode::SERVICE_UNAVAILABLE)),
+                _ => Ok("The Wizard of Oz"),
+            }
+        });
+        assert_eq!(result.await.unwrap(), "The Wizard of Oz");
+        assert_eq!(attempts.get(), 3);
+
+        // OMDb's errors and client errors aren't retried
+        for err in [
+            Error::api("Movie not found!"),
+            Error::Status(StatusCode::UNAUTHORIZED),
+        ] {
+            let attempts = Cell::new(0);
+            let mut err = Some(err);
+            let result: Result<(), Error> = with_retries(3, Duration::from_millis(1), || {
+                attempts.set(attempts.get() + 1);
+                let err = err.take();
+                async move { Err(err.unwrap()) }
+            })
+            .await;
+            assert!(result.is_err());
+            assert_eq!(attempts.get(), 1);
+        }
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let base = Duration::from_millis(100);
+        for attempt in 0..4 {
+            let full = base * 2u32.pow(attempt);
+            let delay = backoff(base, attempt);
+            assert!(delay >= full / 2 && delay <= full);
+        }
+
+        // Many retries, or a huge base, wait at most the longest delay
+        for (base, attempt) in [(base, 40), (Duration::from_secs(u64::MAX), 3)] {
+            let delay = backoff(base, attempt);
+            assert!(delay >= MAX_RETRY_DELAY / 2 && delay <= MAX_RETRY_DELAY);
+        }
+    }
+
+    #[tokio::test]
+    async fn user_agent() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+            r#"{"Response":"True","Search":[],"totalResults":"0"}"#,
+        ]);
+
+        imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get()
+            .await
+            .unwrap();
+        search("batman")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .user_agent("my-app/1.0")
+            .user_agent("my-app/2.0")
+            .get()
+            .await
+            .unwrap();
+
+        let requests: Vec<_> = server
+            .join()
+            .unwrap()
+            .iter()
+            .map(|request| request.to_lowercase())
+            .collect();
+        assert!(requests[0].contains(&format!("user-agent: {}\r\n", USER_AGENT)));
+        assert!(requests[1].contains("user-agent: my-app/2.0\r\n"));
+        assert_eq!(requests[1].matches("user-agent").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn base_url() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":"The Wizard of Oz","imdbID":"tt0032138"}"#,
+        ]);
+
+        let mut query = imdb_id("tt0032138");
+        query.apikey("1a2b3c4d").base_url(&url);
+        assert_eq!(query.plan().unwrap().base_url, url);
+
+        let movie = query.get().await.unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /?v=1&r=json&i=tt0032138&apikey=1a2b3c4d "));
+    }
+
+    #[tokio::test]
+    async fn offline_title() {
+        let (url, server) = crate::mock::serve(vec![WIZARD_OF_OZ]);
+
+        let movie = title("The Wizard of Oz")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get()
+            .await
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(movie.imdb_id, "tt0032138");
+        assert_eq!(movie.kind, Kind::Movie);
+        assert_eq!(movie.runtime_minutes(), Some(102));
+        assert_eq!(movie.rotten_tomatoes(), Some(98));
+        assert_eq!(movie.dvd, "06 Dec 2005");
+        assert_eq!(movie.box_office_usd(), Some(24_668_669));
+        assert_eq!(movie.website, "");
+    }
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        urls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Transport for Recorder {
+        fn get(&self, url: String, _headers: Vec<(String, String)>) -> crate::TransportFuture {
+            self.urls.lock().unwrap().push(url);
+            Box::pin(async { Ok(String::from(WIZARD_OF_OZ)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn offline_timed() {
+        let delay = Duration::from_millis(50);
+        let (url, server) = crate::mock::serve_delayed(delay, vec![WIZARD_OF_OZ]);
+
+        let (movie, elapsed) = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_timed()
+            .await
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(movie.title, "The Wizard of Oz");
+        assert!(elapsed >= delay, "took {:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn offline_transport() {
+        let transport = Arc::new(Recorder::default());
+
+        let movie = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url("http://omdb.invalid")
+            .transport(transport.clone())
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(movie.title, "The Wizard of Oz");
+
+        let urls = transport.urls.lock().unwrap();
+        assert_eq!(
+            *urls,
+            vec!["http://omdb.invalid/?v=1&r=json&i=tt0032138&apikey=1a2b3c4d"]
+        );
+    }
+
+    #[tokio::test]
+    async fn offline_optional() {
+        let (url, server) = crate::mock::serve(vec![
+            WIZARD_OF_OZ,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Response":"False","Error":"Invalid API key!"}"#,
+        ]);
+
+        let mut query = title("The Wizard of Oz");
+        query.apikey("1a2b3c4d").base_url(&url);
+
+        assert!(query.get_optional().await.unwrap().is_some());
+        assert!(query.get_optional().await.unwrap().is_none());
+        assert!(matches!(
+            query.get_optional().await,
+            Err(Error::Api(ApiError::InvalidApiKey))
+        ));
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn offline_raw() {
+        let (url, server) = crate::mock::serve(vec![WIZARD_OF_OZ]);
+
+        let (movie, raw) = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_with_raw()
+            .await
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(movie.title, "The Wizard of Oz");
+        assert_eq!(raw["imdbID"], "tt0032138");
+        assert_eq!(raw["BoxOffice"], "$24,668,669");
+
+        // Valid JSON that isn't a title still keeps the body
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"True","Title":1939,"imdbID":"tt0032138"}"#,
+        ]);
+        let raw = imdb_id("tt0032138")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_with_raw()
+            .await;
+        server.join().unwrap();
+
+        match raw {
+            Err(Error::Decode { ref body, .. }) => assert!(body.contains(r#""Title":1939"#)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn truncated_response() {
+        match decode_body::<FindResponse>(r#"{"Response":"True","Title":"The Wiz"#, false) {
+            Err(Error::Decode { body, source }) => {
+                assert_eq!(body, r#"{"Response":"True","Title":"The Wiz"#);
+                assert!(source.is_eof());
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unrated_episode() {
+        let season = find_result(
+            serde_json::from_str(
+                r#"{"Title":"Fleabag","Season":"2","totalSeasons":"2","Episodes":[
+                    {"Title":"Episode #2.1","Released":"2019-03-04","Episode":"1","imdbRating":"9.0","imdbID":"tt9471404"},
+                    {"Title":"Episode #2.2","Released":"N/A","Episode":"2","imdbRating":"N/A","imdbID":"tt9471406"},
+                    {"Title":"Episode #2.3","Released":"N/A","Episode":"3","imdbRating":null,"imdbID":"tt9471408"}
+                ],"Response":"True"}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(season.episodes.len(), 3);
+        assert_eq!(season.episodes[0].imdb_rating, Some(9.0));
+        assert_eq!(season.episodes[1].imdb_rating, None);
+        assert_eq!(season.episodes[2].imdb_rating, None);
+    }
+
+    #[tokio::test]
+    async fn offline_season() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Title":"Fleabag","imdbID":"tt5687612","Type":"series","totalSeasons":"1","Response":"True"}"#,
+            r#"{"Title":"Fleabag","Season":"1","totalSeasons":"1","Episodes":[
+                {"Title":"Episode #1.1","Released":"2016-07-21","Episode":"1","imdbRating":"8.4","imdbID":"tt5688756"},
+                {"Title":"Episode #1.2","Released":"2016-07-28","Episode":"2","imdbRating":"8.3","imdbID":"tt5701718"}
+            ],"Response":"True"}"#,
+        ]);
+
+        let episodes = title("Fleabag")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_all_episodes()
+            .await
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].contains("&Season=1 "));
+
+        assert_eq!(episodes.len(), 2);
+        assert_eq!(episodes[1].season, 1);
+        assert_eq!(episodes[1].imdb_id, "tt5701718");
+    }
+
+    #[tokio::test]
+    async fn offline_get_season() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Title":"Fleabag","Season":"2","totalSeasons":"2","Episodes":[
+                {"Title":"Episode #2.1","Released":"2019-03-04","Episode":"1","imdbRating":"9.0","imdbID":"tt9471404"},
+                {"Title":"Episode #2.2","Released":"N/A","Episode":"2","imdbRating":"N/A","imdbID":"tt9471406"}
+            ],"Response":"True"}"#,
+            r#"{"Title":"Fleabag","imdbID":"tt5687612","Type":"series","totalSeasons":"2","Response":"True"}"#,
+        ]);
+
+        let season = title("Fleabag")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .season(2)
+            .get_season()
+            .await
+            .unwrap();
+
+        assert_eq!(season.title, "Fleabag");
+        assert_eq!(season.season, 2);
+        assert_eq!(season.total_seasons, 2);
+        assert_eq!(season.episodes.len(), 2);
+        assert_eq!(season.episodes[1].season, 2);
+        assert_eq!(season.episodes[1].released, "");
+
+        // OMDb ignoring the season is an error rather than an empty season
+        let not_season = title("Fleabag")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .season(3)
+            .get_season()
+            .await;
+        assert!(matches!(not_season, Err(Error::Other(_))));
+
+        server.join().unwrap();
+
+        assert!(title("Fleabag")
+            .apikey("1a2b3c4d")
+            .get_season()
+            .await
+            .is_err());
+        assert!(title("Fleabag")
+            .apikey("1a2b3c4d")
+            .season(1)
+            .episode(1)
+            .get_season()
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn offline_search_pages() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Search":[
+                {"Title":"Batman Begins","Year":"2005","imdbID":"tt0372784","Type":"movie","Poster":"N/A"},
+                {"Title":"Batman","Year":"1966–1968","imdbID":"tt0059968","Type":"series","Poster":"N/A"}
+            ],"totalResults":"12","Response":"True"}"#,
+            r#"{"Search":[
+                {"Title":"Batman: The Animated Series","Year":"1992–1995","imdbID":"tt0103359","Type":"series","Poster":"N/A"}
+            ],"totalResults":"12","Response":"True"}"#,
+        ]);
+
+        let results = search("batman")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .get_all()
+            .await
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].contains("&page=2 "));
+
+        let ids: Vec<_> = results.iter().map(|m| m.imdb_id.as_str()).collect();
+        assert_eq!(ids, vec!["tt0372784", "tt0059968", "tt0103359"]);
+        assert_eq!(results[1].kind, Kind::Series);
+        assert_eq!(results[2].year_range(), Some((1992, Some(1995))));
+    }
+
+    #[tokio::test]
+    async fn offline_paginate() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Search":[
+                {"Title":"The Wizard of Oz","Year":"1939","imdbID":"tt0032138","Type":"movie","Poster":"N/A"},
+                {"Title":"The Wizard of Oz","Year":"1925","imdbID":"tt0016544","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"12","Response":"True"}"#,
+            r#"{"Search":[
+                {"Title":"The Wonderful Wizard of Oz","Year":"1910","imdbID":"tt0001463","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"12","Response":"True"}"#,
+        ]);
+
+        let mut pager = search("The Wizard of Oz")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .paginate();
+
+        let mut fetched = Vec::new();
+        while let Some(page) = pager.next().await {
+            let page = page.unwrap();
+            assert_eq!(page.total_results, 12);
+            fetched.extend(page.results);
+        }
+        assert_eq!(fetched.len(), 3);
+        assert!(pager.next().await.is_none());
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].contains("&page=1 "));
+        assert!(requests[1].contains("&page=2 "));
+    }
+
+    #[tokio::test]
+    async fn offline_filtered_pages() {
+        // Three pages of 25 results, none of the second like `batman`
+        let page = |title: &str, first: usize| {
+            let results: Vec<_> = (first..first + PAGE_SIZE)
+                .filter(|i| *i <= 25)
+                .map(|i| {
+                    format!(
+                        r#"{{"Title":"{} {}","Year":"2000","imdbID":"tt{:07}","Type":"movie","Poster":"N/A"}}"#,
+                        title, i, i
+                    )
+                })
+                .collect();
+
+            format!(
+                r#"{{"Search":[{}],"totalResults":"25","Response":"True"}}"#,
+                results.join(",")
+            )
+        };
+        let pages = || {
+            vec![
+                page("Batman", 1),
+                page("A Documentary About Gotham", 11),
+                page("Batman", 21),
+            ]
+        };
+
+        let query = |url: &str| {
+            search("batman").with(|q| q.apikey("1a2b3c4d").min_title_similarity(0.5).base_url(url))
+        };
+        let titles = |results: Vec<SearchResultsMovie>| -> Vec<String> {
+            results.into_iter().map(|m| m.title).collect()
+        };
+        let expected: Vec<_> = (1..=10)
+            .chain(21..=25)
+            .map(|i| format!("Batman {}", i))
+            .collect();
+
+        let (url, server) = crate::mock::serve(pages());
+        let results = query(&url).get_all().await.unwrap();
+        assert_eq!(server.join().unwrap().len(), 3);
+        assert_eq!(titles(results), expected);
+
+        let (url, server) = crate::mock::serve(pages());
+        let items: Vec<_> = query(&url).into_stream().collect().await;
+        assert_eq!(server.join().unwrap().len(), 3);
+        let results = items.into_iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(titles(results), expected);
+
+        // Results 15 to 25 are on the last two pages
+        let (url, server) = crate::mock::serve(pages().split_off(1));
+        let results = query(&url).range(15, 11).await.unwrap();
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("&page=2 "));
+        assert_eq!(titles(results), expected[10..]);
+    }
+
+    #[tokio::test]
+    async fn offline_pages_not_found() {
+        // Claims 3 pages, but page 2 is "Movie not found!"
+        let pages = || {
+            vec![
+                r#"{"Search":[
+                    {"Title":"Batman","Year":"1989","imdbID":"tt0096895","Type":"movie","Poster":"N/A"}
+                ],"totalResults":"25","Response":"True"}"#,
+                r#"{"Response":"False","Error":"Movie not found!"}"#,
+            ]
+        };
+        let query = |url: &str| search("batman").with(|q| q.apikey("1a2b3c4d").base_url(url));
+
+        let (url, server) = crate::mock::serve(pages());
+        let results = query(&url).get_all().await.unwrap();
+        assert_eq!(server.join().unwrap().len(), 2);
+        assert_eq!(results.len(), 1);
+
+        let (url, server) = crate::mock::serve(pages());
+        let (results, failed) = query(&url).get_all_lenient().await.unwrap();
+        assert_eq!(server.join().unwrap().len(), 2);
+        assert_eq!(results.len(), 1);
+        assert!(failed.is_empty());
+
+        let (url, server) = crate::mock::serve(pages());
+        let items: Vec<_> = query(&url).into_stream().collect().await;
+        assert_eq!(server.join().unwrap().len(), 2);
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_ok());
+    }
+
+    #[tokio::test]
+    async fn offline_year_range_pages() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Search":[
+                {"Title":"Batman Beyond","Year":"1999–2001","imdbID":"tt0147746","Type":"series","Poster":"N/A"},
+                {"Title":"Batman Beyond: The Movie","Year":"2000","imdbID":"tt0231237","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"15","Response":"True"}"#,
+            r#"{"Search":[
+                {"Title":"Batman Beyond: Return of the Joker","Year":"2000","imdbID":"tt0233298","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"15","Response":"True"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Search":[
+                {"Title":"Batman Beyond","Year":"1999–2001","imdbID":"tt0147746","Type":"series","Poster":"N/A"},
+                {"Title":"Batman: Mystery of the Batwoman","Year":"2003","imdbID":"tt0346578","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"2","Response":"True"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+        ]);
+        let query = |start, end| {
+            search("batman").with(|q| q.apikey("1a2b3c4d").base_url(&url).year_range(start, end))
+        };
+
+        // Each year is paged through, skipping the one with no results
+        let items: Vec<_> = query(2000, 2002).into_stream().collect().await;
+        let ids: Vec<_> = items
+            .iter()
+            .map(|item| item.as_ref().unwrap().imdb_id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["tt0147746", "tt0231237", "tt0233298", "tt0346578"]
+        );
+
+        let mut pager = query(2004, 2005).paginate();
+        assert!(matches!(
+            pager.next().await,
+            Some(Err(Error::Api(ApiError::NotFound)))
+        ));
+        assert!(pager.next().await.is_none());
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 6);
+        assert!(requests[1].contains("&y=2000&page=2 "));
+        assert!(requests[2].contains("&y=2001&page=1 "));
+        assert!(requests[3].contains("&y=2002&page=1 "));
+
+        let mut backwards = query(2002, 2000).paginate();
+        assert!(matches!(backwards.next().await, Some(Err(Error::Other(_)))));
+        assert!(matches!(
+            query(2000, 2002).range(1, 5).await,
+            Err(Error::Other(_))
+        ));
+        assert!(matches!(
+            query(2000, 2002).get_all_lenient().await,
+            Err(Error::Other(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn offline_retry_normalized() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Search":[
+                {"Title":"Batman","Year":"1989","imdbID":"tt0096895","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"1","Response":"True"}"#,
+        ]);
+
+        let results = search("Battmann!!")
+            .apikey("1a2b3c4d")
+            .base_url(&url)
+            .retry_normalized(true)
+            .get()
+            .await
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("&s=Battmann%21%21&"));
+        assert!(requests[1].contains("&s=Batman&"));
+        assert_eq!(results.results[0].imdb_id, "tt0096895");
+    }
+
+    #[tokio::test]
+    async fn offline_count() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Search":[
+                {"Title":"Batman Begins","Year":"2005","imdbID":"tt0372784","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"542","Response":"True"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+        ]);
+        let query =
+            |search: &str| super::search(search).with(|q| q.apikey("1a2b3c4d").base_url(&url));
+
+        assert_eq!(query("Batman").count().await.unwrap(), 542);
+        assert_eq!(query("Not a Real Movie").count().await.unwrap(), 0);
+        assert_eq!(
+            query("Not a Real Movie")
+                .year_range(2000, 2001)
+                .count()
+                .await
+                .unwrap(),
+            0
+        );
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn offline_errors() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Response":"False","Error":"Movie not found!"}"#,
+            r#"{"Response":"False","Error":"Invalid API key!"}"#,
+        ]);
+
+        let mut query = title("Not a Real Movie");
+        query.apikey("1a2b3c4d").base_url(&url);
+        match query.get().await {
+            Err(Error::Api(ApiError::NotFound)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        match search("batman")
+            .apikey("ffffffff")
+            .base_url(&url)
+            .get()
+            .await
+        {
+            Err(Error::Api(ApiError::InvalidApiKey)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn exact_title() {
+        let (url, server) = crate::mock::serve(vec![
+            r#"{"Search":[
+                {"Title":"The Wizard of Oz","Year":"1939","imdbID":"tt0032138","Type":"movie","Poster":"N/A"},
+                {"Title":"The Wizard of Oz","Year":"1925","imdbID":"tt0016544","Type":"movie","Poster":"N/A"},
+                {"Title":"The Wizard of Oz: Fun in the Land of Oz","Year":"1933","imdbID":"tt0024792","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"3","Response":"True"}"#,
+            r#"{"Search":[
+                {"Title":"The Wizard of Oz","Year":"1939","imdbID":"tt0032138","Type":"movie","Poster":"N/A"}
+            ],"totalResults":"1","Response":"True"}"#,
+            WIZARD_OF_OZ,
+        ]);
+
+        let mut query = title("The Wizard of Oz");
+        query.apikey("1a2b3c4d").base_url(&url).exact(true);
+        match query.get().await {
+            Err(Error::Api(ApiError::MultipleResults)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let movie = query.year(1939).get().await.unwrap();
+        assert_eq!(movie.imdb_id, "tt0032138");
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].contains("s=The+Wizard+of+Oz"));
+        assert!(requests[2].contains("t=The+Wizard+of+Oz"));
+    }
+
+    #[test]
+    fn request_timeout() {
+        let mut query = search("batman");
+        let request = build_request(&query.http, query.params(), &query.headers)
+            .build()
+            .unwrap();
+        assert_eq!(request.timeout(), None);
+
+        query.timeout(Duration::from_secs(5));
+        let request = build_request(&query.http, query.params(), &query.headers)
+            .build()
+            .unwrap();
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn request_headers() {
+        let mut query = search("batman");
+        query.request_id("abc-122").request_id("abc-123");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Gateway", "omdb".parse().unwrap());
+        query.headers(headers);
+
+        let request = build_request(&query.http, query.params(), &query.headers)
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["x-request-id"], "abc-123");
+        assert_eq!(request.headers().get_all("x-request-id").iter().count(), 1);
+        assert_eq!(request.headers()["x-gateway"], "omdb");
+    }
+
+    #[test]
+    fn queries_as_keys() {
+        use std::collections::HashSet;
+
+        let mut finds = HashSet::new();
+        finds.insert(title("The Wizard of Oz").year(1939).clone());
+        finds.insert(title("The Wizard of Oz").year(1939).clone());
+        finds.insert(title("The Wizard of Oz").year(1985).clone());
+        finds.insert(FindQuery::from_query_string("v=1&t=The+Wizard+of+Oz&y=1939").unwrap());
+        // How the request is sent isn't part of the query
+        finds.insert(
+            title("The Wizard of Oz")
+                .year(1939)
+                .base_url("http://localhost:8080")
+                .timeout(Duration::from_secs(5))
+                .retries(3)
+                .with_client(reqwest::Client::new())
+                .clone(),
+        );
+        finds.insert(
+            title("The Wizard of Oz")
+                .year(1939)
+                .api_version("2")
+                .clone(),
+        );
+        assert_eq!(finds.len(), 3);
+
+        let mut searches = HashSet::new();
+        searches.insert(search("batman").page(2).clone());
+        searches.insert(search("batman").page(2).clone());
+        assert_eq!(searches.len(), 1);
+    }
 
-        Ok(response.into())
+    #[test]
+    fn find_query_string_invalid() {
+        assert!(FindQuery::from_query_string("t=batman&type=book").is_err());
+        assert!(FindQuery::from_query_string("y=1989").is_err());
     }
 }
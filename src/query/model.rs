@@ -1,6 +1,14 @@
-use crate::{Kind, Movie, SearchResults, SearchResultsMovie};
+use crate::{Episode, Kind, Movie, Rating, SearchResults, SearchResultsMovie};
 use serde::Deserialize;
 
+/// A field from OMDb, with a missing value or OMDb's `N/A` made empty.
+fn na_to_empty(value: Option<String>) -> String {
+    match value {
+        Some(value) if value != "N/A" => value,
+        _ => String::new(),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct FindResponse {
     #[serde(rename = "Response")]
@@ -11,6 +19,8 @@ pub struct FindResponse {
 
     #[serde(rename = "Title")]
     pub title: Option<String>,
+    #[serde(rename = "OriginalTitle")]
+    pub original_title: Option<String>,
     #[serde(rename = "Year")]
     pub year: Option<String>,
     #[serde(rename = "Rated")]
@@ -47,36 +57,143 @@ pub struct FindResponse {
     pub imdb_id: Option<String>,
     #[serde(rename = "Type")]
     pub kind: Option<String>,
+    #[serde(rename = "Ratings")]
+    pub ratings: Option<Vec<RatingResponse>>,
+    #[serde(rename = "DVD")]
+    pub dvd: Option<String>,
+    #[serde(rename = "BoxOffice")]
+    pub box_office: Option<String>,
+    #[serde(rename = "Production")]
+    pub production: Option<String>,
+    #[serde(rename = "Website")]
+    pub website: Option<String>,
+
+    // A season's episodes, when only `Season` was requested
+    #[serde(rename = "Episodes")]
+    pub episodes: Option<Vec<EpisodeResponse>>,
+
+    #[serde(rename = "totalSeasons")]
+    pub total_seasons: Option<String>,
+
+    // Only used to infer the kind when `Type` is missing, and to number
+    // a season's episodes
+    #[serde(rename = "Season")]
+    pub season: Option<String>,
+    #[serde(rename = "Episode")]
+    pub episode: Option<String>,
+}
+
+impl FindResponse {
+    /// The kind of media, inferred from the other fields if OMDb didn't say.
+    fn kind(&self) -> Kind {
+        if let Some(kind) = self.kind.as_deref().and_then(|kind| kind.parse().ok()) {
+            kind
+        } else if self.season.is_some() && self.episode.is_some() {
+            Kind::Episode
+        } else if self.total_seasons.is_some() || self.episodes.is_some() {
+            Kind::Series
+        } else {
+            Kind::Movie
+        }
+    }
 }
 
 impl From<FindResponse> for Movie {
     fn from(find: FindResponse) -> Movie {
+        let kind = find.kind();
+        let season = find
+            .season
+            .as_deref()
+            .and_then(|season| season.parse().ok())
+            .unwrap_or_default();
+
         Movie {
-            title: find.title.unwrap_or_default(),
-            year: find.year.unwrap_or_default(),
-            rated: find.rated.unwrap_or_default(),
-            released: find.released.unwrap_or_default(),
-            runtime: find.runtime.unwrap_or_default(),
-            genre: find.genre.unwrap_or_default(),
-            director: find.director.unwrap_or_default(),
-            writer: find.writer.unwrap_or_default(),
-            actors: find.actors.unwrap_or_default(),
-            plot: find.plot.unwrap_or_default(),
-            language: find.language.unwrap_or_default(),
-            country: find.country.unwrap_or_default(),
-            awards: find.awards.unwrap_or_default(),
-            poster: find.poster.unwrap_or_default(),
-            metascore: find.metascore.unwrap_or_default(),
-            imdb_rating: find.imdb_rating.unwrap_or_default(),
-            imdb_votes: find.imdb_votes.unwrap_or_default(),
-            imdb_id: find.imdb_id.unwrap_or_default(),
-            kind: match find.kind {
-                Some(kind_string) => match Kind::from_str(&kind_string) {
-                    Some(kind) => kind,
-                    None => Kind::Movie,
-                },
-                None => Kind::Movie,
-            },
+            title: na_to_empty(find.title),
+            original_title: find.original_title.filter(|title| title != "N/A"),
+            year: na_to_empty(find.year),
+            rated: na_to_empty(find.rated),
+            released: na_to_empty(find.released),
+            runtime: na_to_empty(find.runtime),
+            genre: na_to_empty(find.genre),
+            director: na_to_empty(find.director),
+            writer: na_to_empty(find.writer),
+            actors: na_to_empty(find.actors),
+            plot: na_to_empty(find.plot),
+            language: na_to_empty(find.language),
+            country: na_to_empty(find.country),
+            awards: na_to_empty(find.awards),
+            poster: na_to_empty(find.poster),
+            metascore: na_to_empty(find.metascore),
+            imdb_rating: na_to_empty(find.imdb_rating),
+            imdb_votes: na_to_empty(find.imdb_votes),
+            imdb_id: na_to_empty(find.imdb_id),
+            kind,
+            dvd: na_to_empty(find.dvd),
+            box_office: na_to_empty(find.box_office),
+            production: na_to_empty(find.production),
+            website: na_to_empty(find.website),
+            total_seasons: na_to_empty(find.total_seasons),
+            ratings: find
+                .ratings
+                .unwrap_or_default()
+                .into_iter()
+                .map(|rating| rating.into())
+                .collect(),
+            episodes: find
+                .episodes
+                .unwrap_or_default()
+                .into_iter()
+                .map(|episode| Episode {
+                    season,
+                    ..episode.into()
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EpisodeResponse {
+    #[serde(rename = "Title")]
+    pub title: Option<String>,
+    #[serde(rename = "Released")]
+    pub released: Option<String>,
+    #[serde(rename = "Episode")]
+    pub episode: Option<String>,
+    #[serde(rename = "imdbRating")]
+    pub imdb_rating: Option<String>,
+    #[serde(rename = "imdbID")]
+    pub imdb_id: Option<String>,
+}
+
+impl From<EpisodeResponse> for Episode {
+    fn from(episode: EpisodeResponse) -> Episode {
+        Episode {
+            // Filled in from the season response the episode is in
+            season: 0,
+            title: na_to_empty(episode.title),
+            released: na_to_empty(episode.released),
+            episode: na_to_empty(episode.episode),
+            // `N/A` and a missing rating don't parse
+            imdb_rating: episode.imdb_rating.and_then(|rating| rating.parse().ok()),
+            imdb_id: na_to_empty(episode.imdb_id),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RatingResponse {
+    #[serde(rename = "Source")]
+    pub source: String,
+    #[serde(rename = "Value")]
+    pub value: String,
+}
+
+impl From<RatingResponse> for Rating {
+    fn from(rating: RatingResponse) -> Rating {
+        Rating {
+            source: rating.source,
+            value: rating.value,
         }
     }
 }
@@ -108,6 +225,7 @@ impl From<SearchResponse> for SearchResults {
                 .total_results
                 .map(|s| s.parse::<usize>().unwrap_or_default())
                 .unwrap_or_default(),
+            page: 1,
         }
     }
 }
@@ -129,15 +247,12 @@ pub struct SearchResponseMovie {
 impl From<SearchResponseMovie> for SearchResultsMovie {
     fn from(srm: SearchResponseMovie) -> SearchResultsMovie {
         SearchResultsMovie {
-            title: srm.title.unwrap_or_default(),
-            year: srm.year.unwrap_or_default(),
-            poster: srm.poster.unwrap_or_default(),
-            imdb_id: srm.imdb_id.unwrap_or_default(),
+            title: na_to_empty(srm.title),
+            year: na_to_empty(srm.year),
+            poster: na_to_empty(srm.poster),
+            imdb_id: na_to_empty(srm.imdb_id),
             kind: match srm.kind {
-                Some(kind_string) => match Kind::from_str(&kind_string) {
-                    Some(kind) => kind,
-                    None => Kind::Movie,
-                },
+                Some(kind_string) => kind_string.parse().unwrap_or(Kind::Movie),
                 None => Kind::Movie,
             },
         }
@@ -0,0 +1,137 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{ApiError, Error};
+
+/// Counts the successful requests from a `Client`, optionally failing
+/// requests once a limit is reached.
+#[derive(Debug)]
+pub struct Quota {
+    state: Mutex<Limit>,
+}
+
+#[derive(Debug)]
+struct Limit {
+    // Requests sent or being sent in the current window
+    count: u64,
+    // The most requests per window, and the window's length
+    limit: Option<(u64, Duration)>,
+    // When the window started, only kept with a limit so counting
+    // alone never reads the clock, which WebAssembly doesn't have
+    started: Option<Instant>,
+}
+
+impl Quota {
+    /// A counter without a limit.
+    pub fn new() -> Quota {
+        Quota {
+            state: Mutex::new(Limit {
+                count: 0,
+                limit: None,
+                started: None,
+            }),
+        }
+    }
+
+    /// Allow `limit` requests every `window`, starting a new window now.
+    pub fn set_limit(&self, limit: u64, window: Duration) {
+        let mut state = self.lock();
+        state.limit = Some((limit, window));
+        state.started = Some(Instant::now());
+        state.count = 0;
+    }
+
+    /// The number of requests in the current window, counting those
+    /// still being sent.
+    pub fn count(&self) -> u64 {
+        let mut state = self.lock();
+        Quota::roll(&mut state);
+        state.count
+    }
+
+    /// Resets the count to zero, starting a new window.
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        if state.limit.is_some() {
+            state.started = Some(Instant::now());
+        }
+        state.count = 0;
+    }
+
+    /// Counts a request about to be sent, or fails with
+    /// `ApiError::LimitReached` if the limit has been reached.
+    ///
+    /// Checking and counting happen together, so concurrent requests
+    /// can't all pass the check before any of them is counted.
+    pub fn reserve(&self) -> Result<(), Error> {
+        let mut state = self.lock();
+        Quota::roll(&mut state);
+
+        match state.limit {
+            Some((limit, _)) if state.count >= limit => Err(Error::Api(ApiError::LimitReached)),
+            _ => {
+                state.count += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Uncounts a reserved request that failed.
+    pub fn release(&self) {
+        let mut state = self.lock();
+        // The window may have rolled over since the request was counted
+        state.count = state.count.saturating_sub(1);
+    }
+
+    /// Starts a new window if the current one is over.
+    fn roll(state: &mut Limit) {
+        if let (Some((_, window)), Some(started)) = (state.limit, state.started) {
+            if started.elapsed() >= window {
+                state.started = Some(Instant::now());
+                state.count = 0;
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Limit> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit() {
+        let quota = Quota::new();
+        quota.reserve().unwrap();
+        quota.reserve().unwrap();
+        assert_eq!(quota.count(), 2);
+        quota.release();
+        assert_eq!(quota.count(), 1);
+        quota.reset();
+        assert!(quota.lock().started.is_none());
+
+        quota.set_limit(1, Duration::from_secs(60));
+        assert_eq!(quota.count(), 0);
+        quota.reserve().unwrap();
+        assert!(matches!(
+            quota.reserve(),
+            Err(Error::Api(ApiError::LimitReached))
+        ));
+        assert_eq!(quota.count(), 1);
+
+        // A failed request frees its place
+        quota.release();
+        assert!(quota.reserve().is_ok());
+
+        quota.reset();
+        assert!(quota.reserve().is_ok());
+
+        // A window that's already over resets the count
+        quota.set_limit(1, Duration::from_secs(0));
+        quota.reserve().unwrap();
+        assert!(quota.reserve().is_ok());
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(not(target_arch = "wasm32"))]
+use futures_util::future::BoxFuture;
+#[cfg(target_arch = "wasm32")]
+use futures_util::future::LocalBoxFuture;
+use std::fmt;
+
+use crate::Error;
+
+/// Sends requests to OMDb in place of `reqwest`, such as with the
+/// browser's `fetch` in WebAssembly.
+///
+/// Queries given a transport with `FindQuery::transport` send every
+/// request through it. Rate limits, retries and caching still apply,
+/// but the query's `timeout` and `with_client` are up to the transport.
+///
+/// # WebAssembly
+///
+/// On `wasm32`, a `TransportFuture` doesn't need to be `Send`, so a
+/// transport can await JavaScript promises such as `fetch`'s. There's
+/// no clock or tokio timer there, so leave off the options that need
+/// one: `retries`, `Client::with_rate_limit`, `Client::with_cache`,
+/// `Client::with_daily_limit`, `Client::with_request_limit` and
+/// `FindQuery::get_timed`. Everything else, including
+/// `Client::request_count`, works without tokio.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug)]
+/// struct Canned;
+///
+/// impl omdb::Transport for Canned {
+///     fn get(&self, _url: String, _headers: Vec<(String, String)>) -> omdb::TransportFuture {
+///         Box::pin(async { Ok(String::from(r#"{"Response":"False","Error":"Movie not found!"}"#)) })
+///     }
+/// }
+///
+/// let mut query = omdb::title("The Wizard of Oz");
+/// query.apikey("1a2b3c4d").transport(std::sync::Arc::new(Canned));
+/// ```
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Send a GET request to `url`, which already has every query
+    /// parameter, along with the extra `headers`, returning the
+    /// response body.
+    ///
+    /// A response with an unsuccessful status should fail with
+    /// `Error::Status`, so it can be retried.
+    fn get(&self, url: String, headers: Vec<(String, String)>) -> TransportFuture;
+}
+
+/// The response body a `Transport` resolves to.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture = BoxFuture<'static, Result<String, Error>>;
+
+/// The response body a `Transport` resolves to.
+///
+/// JavaScript futures aren't `Send`, so neither is this on `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture = LocalBoxFuture<'static, Result<String, Error>>;